@@ -0,0 +1,223 @@
+//! Soil, evapotranspiration, and water balance from the forecast and archive APIs.
+
+use crate::{
+    stats::Summary,
+    units::Units,
+    variables::{Block, Variable},
+    DailySeries, OpenMeteoServer, Place,
+};
+use chrono::NaiveDate;
+use serde_json::Value;
+
+/// Soil layers of one endpoint as (hourly variable, depth label).
+pub struct SoilLayers {
+    temperature: &'static [(&'static str, &'static str)],
+    moisture: &'static [(&'static str, &'static str)],
+}
+
+/// The forecast API models soil temperature at points and moisture in layers.
+pub const FORECAST_SOIL: SoilLayers = SoilLayers {
+    temperature: &[
+        ("soil_temperature_0cm", "0cm"),
+        ("soil_temperature_6cm", "6cm"),
+        ("soil_temperature_18cm", "18cm"),
+        ("soil_temperature_54cm", "54cm"),
+    ],
+    moisture: &[
+        ("soil_moisture_0_to_1cm", "0-1cm"),
+        ("soil_moisture_1_to_3cm", "1-3cm"),
+        ("soil_moisture_3_to_9cm", "3-9cm"),
+        ("soil_moisture_9_to_27cm", "9-27cm"),
+        ("soil_moisture_27_to_81cm", "27-81cm"),
+    ],
+};
+
+/// The archive (ERA5) uses four layers for both soil temperature and moisture.
+pub const ARCHIVE_SOIL: SoilLayers = SoilLayers {
+    temperature: &[
+        ("soil_temperature_0_to_7cm", "0-7cm"),
+        ("soil_temperature_7_to_28cm", "7-28cm"),
+        ("soil_temperature_28_to_100cm", "28-100cm"),
+        ("soil_temperature_100_to_255cm", "100-255cm"),
+    ],
+    moisture: &[
+        ("soil_moisture_0_to_7cm", "0-7cm"),
+        ("soil_moisture_7_to_28cm", "7-28cm"),
+        ("soil_moisture_28_to_100cm", "28-100cm"),
+        ("soil_moisture_100_to_255cm", "100-255cm"),
+    ],
+};
+
+impl OpenMeteoServer {
+    /// Extra variables requested from the forecast and archive endpoints.
+    pub fn agriculture_variables(&self, soil: &SoilLayers) -> Vec<Variable> {
+        let hourly = soil
+            .temperature
+            .iter()
+            .chain(soil.moisture)
+            .map(|(name, _)| *name)
+            .chain(["vapour_pressure_deficit"])
+            .map(|name| Variable {
+                block: Block::Hourly,
+                name,
+            });
+        hourly
+            .chain([Variable {
+                block: Block::Daily,
+                name: "et0_fao_evapotranspiration",
+            }])
+            .collect()
+    }
+
+    /// Past and forecast days with a running water balance. Archive days
+    /// outside `past` are left out.
+    pub fn format_agriculture_weather(
+        &self,
+        archive: Option<&Value>,
+        forecast: Option<&Value>,
+        past: std::ops::Range<NaiveDate>,
+        place: &Place,
+        units: Units,
+    ) -> String {
+        let header_data = forecast.or(archive).unwrap_or(&Value::Null);
+        let mut report = format!(
+            "🌱 Agricultural Weather\nLocation: {}{}{}\n",
+            place,
+            self.format_elevation(header_data),
+            self.format_timezone(header_data)
+        );
+
+        let mean = |summary: &Summary| summary.sum / summary.count as f64;
+        let max = |summary: &Summary| summary.max;
+
+        // (precipitation, ET0) totals across both sections
+        let mut totals = (Summary::default(), Summary::default());
+        let mut balance = 0.0;
+        let mut precip_unit = units.precipitation.label().to_string();
+
+        let sections = [
+            (
+                "📜 Past days (historical archive)",
+                archive,
+                &ARCHIVE_SOIL,
+                Some(&past),
+            ),
+            ("🔮 Forecast", forecast, &FORECAST_SOIL, None),
+        ];
+        for (title, data, soil, window) in sections {
+            let Some(data) = data else {
+                continue;
+            };
+            let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+            let hourly_units = data.get("hourly_units").unwrap_or(&Value::Null);
+            let unit = |units: &Value, key: &str, fallback: &str| {
+                units
+                    .get(key)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(fallback)
+                    .to_string()
+            };
+            precip_unit = unit(
+                daily_units,
+                "precipitation_sum",
+                units.precipitation.label(),
+            );
+            let et0_unit = unit(daily_units, "et0_fao_evapotranspiration", &precip_unit);
+            let temp_unit = unit(
+                hourly_units,
+                soil.temperature[0].0,
+                units.temperature.label(),
+            );
+            let moisture_unit = unit(hourly_units, soil.moisture[0].0, "m³/m³");
+            let vpd_unit = unit(hourly_units, "vapour_pressure_deficit", "kPa");
+
+            let precipitation = self.daily_series(data, "precipitation_sum");
+            let et0 = self.daily_series(data, "et0_fao_evapotranspiration");
+            let vpd = self.daily_from_hourly(data, "vapour_pressure_deficit", max);
+            let soil_temperature: Vec<(&str, DailySeries)> = soil
+                .temperature
+                .iter()
+                .map(|(key, depth)| (*depth, self.daily_from_hourly(data, key, mean)))
+                .collect();
+            let soil_moisture: Vec<(&str, DailySeries)> = soil
+                .moisture
+                .iter()
+                .map(|(key, depth)| (*depth, self.daily_from_hourly(data, key, mean)))
+                .collect();
+
+            let empty_vec = vec![];
+            let dates = data
+                .get("daily")
+                .and_then(|daily| daily.get("time"))
+                .and_then(|v| v.as_array())
+                .unwrap_or(&empty_vec);
+
+            report.push_str(&format!("\n{}:\n", title));
+            let in_window = |date: &&str| match window {
+                Some(window) => self
+                    .validate_date(date)
+                    .is_ok_and(|date| window.contains(&date)),
+                None => true,
+            };
+            for date in dates.iter().filter_map(|v| v.as_str()).filter(in_window) {
+                let water = match (precipitation.get(date), et0.get(date)) {
+                    (Some(p), Some(e)) => {
+                        totals.0.push(*p);
+                        totals.1.push(*e);
+                        balance += p - e;
+                        format!(
+                            "☔ {:.1}{} − ET0 {:.1}{} = {:+.1}{} (running total {:+.1}{})",
+                            p,
+                            precip_unit,
+                            e,
+                            et0_unit,
+                            p - e,
+                            precip_unit,
+                            balance,
+                            precip_unit
+                        )
+                    }
+                    _ => "☔ water balance n/a".to_string(),
+                };
+                let vpd = match vpd.get(date) {
+                    Some(v) => format!("{:.2}{}", v, vpd_unit),
+                    None => "n/a".to_string(),
+                };
+                let layers = |series: &[(&str, DailySeries)], precision: usize, unit: &str| {
+                    series
+                        .iter()
+                        .map(|(depth, values)| match values.get(date) {
+                            Some(v) => format!("{} {:.*}{}", depth, precision, v, unit),
+                            None => format!("{} n/a", depth),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                };
+
+                report.push_str(&format!(
+                    "{}: {}\n   🌡️ Soil temperature: {}\n   💧 Soil moisture: {}\n   🌬️ Max VPD: {}\n",
+                    date,
+                    water,
+                    layers(&soil_temperature, 1, &temp_unit),
+                    layers(&soil_moisture, 3, &moisture_unit),
+                    vpd
+                ));
+            }
+        }
+
+        if !totals.0.is_empty() {
+            report.push_str(&format!(
+                "\n💧 Water balance over {} days: precipitation {:.1}{}, ET0 {:.1}{}, net {:+.1}{}\n",
+                totals.0.count,
+                totals.0.sum,
+                precip_unit,
+                totals.1.sum,
+                precip_unit,
+                balance,
+                precip_unit
+            ));
+        }
+
+        report
+    }
+}
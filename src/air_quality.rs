@@ -0,0 +1,177 @@
+//! Air quality and pollen from the air quality API.
+
+use crate::{config::Api, OpenMeteoServer, Place, QueryOptions};
+use serde_json::Value;
+
+impl OpenMeteoServer {
+    pub async fn fetch_air_quality(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/air-quality?latitude={}&longitude={}&current=european_aqi,us_aqi,pm10,pm2_5,carbon_monoxide,nitrogen_dioxide,sulphur_dioxide,ozone,alder_pollen,birch_pollen,grass_pollen,mugwort_pollen,olive_pollen,ragweed_pollen{}",
+            self.base_url(Api::AirQuality),
+            latitude,
+            longitude,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Air Quality API").await
+    }
+
+    pub fn format_air_quality(&self, data: &Value, place: &Place) -> String {
+        let current = data.get("current").unwrap_or(&Value::Null);
+        let current_units = data.get("current_units").unwrap_or(&Value::Null);
+
+        let value = |key: &str| current.get(key).and_then(|v| v.as_f64());
+        let unit = |key: &str| {
+            current_units
+                .get(key)
+                .and_then(|v| v.as_str())
+                .unwrap_or("μg/m³")
+        };
+
+        let time = current
+            .get("time")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown");
+
+        let mut report = format!(
+            "🌍 Current Air Quality\nLocation: {}{}\nTime: {}\n\n",
+            place,
+            self.format_timezone(data),
+            time
+        );
+
+        if let Some(aqi) = value("european_aqi") {
+            report.push_str(&format!(
+                "🇪🇺 European AQI: {:.0} ({})\n",
+                aqi,
+                self.get_european_aqi_category(aqi)
+            ));
+        }
+        if let Some(aqi) = value("us_aqi") {
+            report.push_str(&format!(
+                "🇺🇸 US AQI: {:.0} ({})\n",
+                aqi,
+                self.get_us_aqi_category(aqi)
+            ));
+        }
+
+        report.push_str("\n🏭 Pollutants:\n");
+        let pollutants: [(&str, &str, Option<[f64; 5]>); 6] = [
+            ("pm2_5", "PM2.5", Some([10.0, 20.0, 25.0, 50.0, 75.0])),
+            ("pm10", "PM10", Some([20.0, 40.0, 50.0, 100.0, 150.0])),
+            (
+                "ozone",
+                "Ozone (O₃)",
+                Some([50.0, 100.0, 130.0, 240.0, 380.0]),
+            ),
+            (
+                "nitrogen_dioxide",
+                "Nitrogen dioxide (NO₂)",
+                Some([40.0, 90.0, 120.0, 230.0, 340.0]),
+            ),
+            (
+                "sulphur_dioxide",
+                "Sulphur dioxide (SO₂)",
+                Some([100.0, 200.0, 350.0, 500.0, 750.0]),
+            ),
+            ("carbon_monoxide", "Carbon monoxide (CO)", None),
+        ];
+        for (key, label, bands) in pollutants {
+            let Some(amount) = value(key) else {
+                continue;
+            };
+            let category = bands
+                .map(|bands| format!(" ({})", self.get_pollutant_category(amount, &bands)))
+                .unwrap_or_default();
+            report.push_str(&format!(
+                "• {}: {:.1}{}{}\n",
+                label,
+                amount,
+                unit(key),
+                category
+            ));
+        }
+
+        let pollen: Vec<(&str, f64)> = [
+            ("alder_pollen", "Alder"),
+            ("birch_pollen", "Birch"),
+            ("grass_pollen", "Grass"),
+            ("mugwort_pollen", "Mugwort"),
+            ("olive_pollen", "Olive"),
+            ("ragweed_pollen", "Ragweed"),
+        ]
+        .into_iter()
+        .filter_map(|(key, label)| value(key).map(|amount| (label, amount)))
+        .collect();
+
+        report.push_str("\n🌼 Pollen:\n");
+        if pollen.is_empty() {
+            report.push_str("Pollen data is not available for this region (Europe only).\n");
+        } else {
+            let pollen_unit = current_units
+                .get("birch_pollen")
+                .and_then(|v| v.as_str())
+                .unwrap_or("grains/m³");
+            for (label, amount) in pollen {
+                report.push_str(&format!(
+                    "• {}: {:.0} {} ({})\n",
+                    label,
+                    amount,
+                    pollen_unit,
+                    self.get_pollen_category(amount)
+                ));
+            }
+        }
+
+        report
+    }
+
+    fn get_european_aqi_category(&self, aqi: f64) -> &'static str {
+        match aqi {
+            a if a <= 20.0 => "Good",
+            a if a <= 40.0 => "Fair",
+            a if a <= 60.0 => "Moderate",
+            a if a <= 80.0 => "Poor",
+            a if a <= 100.0 => "Very poor",
+            _ => "Extremely poor",
+        }
+    }
+
+    fn get_us_aqi_category(&self, aqi: f64) -> &'static str {
+        match aqi {
+            a if a <= 50.0 => "Good",
+            a if a <= 100.0 => "Moderate",
+            a if a <= 150.0 => "Unhealthy for sensitive groups",
+            a if a <= 200.0 => "Unhealthy",
+            a if a <= 300.0 => "Very unhealthy",
+            _ => "Hazardous",
+        }
+    }
+
+    /// Maps a pollutant concentration onto the European AQI bands, given the
+    /// upper limits of the Good, Fair, Moderate, Poor and Very poor bands.
+    fn get_pollutant_category(&self, concentration: f64, bands: &[f64; 5]) -> &'static str {
+        const LABELS: [&str; 5] = ["Good", "Fair", "Moderate", "Poor", "Very poor"];
+        bands
+            .iter()
+            .zip(LABELS)
+            .find(|(limit, _)| concentration <= **limit)
+            .map(|(_, label)| label)
+            .unwrap_or("Extremely poor")
+    }
+
+    fn get_pollen_category(&self, grains: f64) -> &'static str {
+        match grains {
+            g if g < 1.0 => "None",
+            g if g <= 10.0 => "Low",
+            g if g <= 50.0 => "Moderate",
+            g if g <= 200.0 => "High",
+            _ => "Very high",
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Historical weather from the archive API.
+
+use crate::{
+    config::Api,
+    stats::Summary,
+    units::Units,
+    variables::{self, Block},
+    Coordinates, OpenMeteoServer, Place, QueryOptions,
+};
+use serde_json::Value;
+
+/// Daily variables requested from the historical archive.
+const HISTORICAL_DAILY_VARIABLES: &[&str] = &[
+    "weather_code",
+    "temperature_2m_max",
+    "temperature_2m_min",
+    "temperature_2m_mean",
+    "apparent_temperature_max",
+    "apparent_temperature_min",
+    "apparent_temperature_mean",
+    "sunrise",
+    "sunset",
+    "daylight_duration",
+    "sunshine_duration",
+    "precipitation_sum",
+    "rain_sum",
+    "snowfall_sum",
+    "precipitation_hours",
+    "wind_speed_10m_max",
+    "wind_gusts_10m_max",
+    "wind_direction_10m_dominant",
+];
+
+impl OpenMeteoServer {
+    pub async fn fetch_historical_weather(
+        &self,
+        locations: &[Coordinates],
+        start_date: &str,
+        end_date: &str,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/archive?{}&start_date={}&end_date={}{}{}{}",
+            self.base_url(Api::Archive),
+            Coordinates::to_query(locations),
+            start_date,
+            end_date,
+            variables::query(Block::Daily, HISTORICAL_DAILY_VARIABLES, &options.variables),
+            variables::query(Block::Hourly, &[], &options.variables),
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo API").await
+    }
+
+    pub fn format_historical_weather(
+        &self,
+        data: &Value,
+        place: &Place,
+        start_date: &str,
+        end_date: &str,
+        units: Units,
+    ) -> String {
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let dates = daily
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let temp_max = daily
+            .get("temperature_2m_max")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let temp_min = daily
+            .get("temperature_2m_min")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let temp_mean = daily
+            .get("temperature_2m_mean")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let precipitation = daily
+            .get("precipitation_sum")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let temp_unit = daily_units
+            .get("temperature_2m_max")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.temperature.label());
+        let precip_unit = daily_units
+            .get("precipitation_sum")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.precipitation.label());
+
+        let mut history = format!(
+            "🌍 Historical Weather Data\nLocation: {}{}\nPeriod: {} to {}\n\n",
+            place,
+            self.format_timezone(data),
+            start_date,
+            end_date
+        );
+
+        let mut highs = Summary::default();
+        let mut lows = Summary::default();
+        let mut means = Summary::default();
+        let mut precips = Summary::default();
+
+        for i in 0..dates.len() {
+            if let (Some(max_temp), Some(min_temp), Some(mean_temp), Some(precip)) = (
+                temp_max.get(i).and_then(|v| v.as_f64()),
+                temp_min.get(i).and_then(|v| v.as_f64()),
+                temp_mean.get(i).and_then(|v| v.as_f64()),
+                precipitation.get(i).and_then(|v| v.as_f64()),
+            ) {
+                highs.push(max_temp);
+                lows.push(min_temp);
+                means.push(mean_temp);
+                precips.push(precip);
+            }
+        }
+
+        if let (Some(avg_high), Some(avg_low), Some(avg_mean), Some(avg_precip)) =
+            (highs.mean(), lows.mean(), means.mean(), precips.mean())
+        {
+            history.push_str(&format!(
+                "📊 Summary Statistics ({} days):\n🌡️ Average High: {:.1}{}\n🌡️ Average Low: {:.1}{}\n🌡️ Average Mean: {:.1}{}\n☔ Total Precipitation: {:.1}{}\n☔ Average Daily Precipitation: {:.1}{}\n\n",
+                precips.count,
+                avg_high, temp_unit,
+                avg_low, temp_unit,
+                avg_mean, temp_unit,
+                precips.sum, precip_unit,
+                avg_precip, precip_unit
+            ));
+        }
+
+        history.push_str("📅 Daily Data (first 5 days):\n");
+        for (i, date) in dates.iter().enumerate().take(5) {
+            let date = date.as_str().unwrap_or("Unknown");
+            let max_temp = temp_max.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let min_temp = temp_min.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let precip = precipitation.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+            history.push_str(&format!(
+                "{}: {:.1}{} / {:.1}{}, {:.1}{}\n",
+                date, max_temp, temp_unit, min_temp, temp_unit, precip, precip_unit
+            ));
+        }
+
+        history
+    }
+}
@@ -0,0 +1,122 @@
+//! In-memory cache of API responses keyed by request URL.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde_json::Value;
+
+/// Responses younger than `ttl`, at most `capacity` of them. A zero TTL or
+/// capacity disables the cache.
+#[derive(Debug)]
+pub struct ResponseCache {
+    ttl: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<String, (Instant, Value)>>,
+}
+
+impl ResponseCache {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        !self.ttl.is_zero() && self.capacity > 0
+    }
+
+    pub fn get(&self, key: &str) -> Option<Value> {
+        if !self.enabled() {
+            return None;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get(key) {
+            Some((stored, value)) if stored.elapsed() < self.ttl => Some(value.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Stores a response, making room by dropping expired entries and then
+    /// the oldest one.
+    pub fn insert(&self, key: String, value: Value) {
+        if !self.enabled() {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= self.capacity && !entries.contains_key(&key) {
+            entries.retain(|_, (stored, _)| stored.elapsed() < self.ttl);
+            if entries.len() >= self.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (stored, _))| *stored)
+                    .map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(key, (Instant::now(), value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::thread::sleep;
+
+    #[test]
+    fn entries_expire_after_the_ttl() {
+        let cache = ResponseCache::new(Duration::from_millis(50), 10);
+        cache.insert("a".to_string(), json!(1));
+        assert_eq!(cache.get("a"), Some(json!(1)));
+
+        sleep(Duration::from_millis(60));
+        assert_eq!(cache.get("a"), None);
+        assert!(cache.entries.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn insert_evicts_expired_entries_then_the_oldest() {
+        let cache = ResponseCache::new(Duration::from_secs(60), 2);
+        cache.insert("a".to_string(), json!(1));
+        sleep(Duration::from_millis(2));
+        cache.insert("b".to_string(), json!(2));
+        sleep(Duration::from_millis(2));
+        cache.insert("c".to_string(), json!(3));
+        assert_eq!(cache.get("a"), None);
+        assert_eq!(cache.get("b"), Some(json!(2)));
+        assert_eq!(cache.get("c"), Some(json!(3)));
+
+        // Replacing an entry does not evict another one.
+        cache.insert("c".to_string(), json!(4));
+        assert_eq!(cache.get("b"), Some(json!(2)));
+        assert_eq!(cache.get("c"), Some(json!(4)));
+
+        let cache = ResponseCache::new(Duration::from_millis(50), 2);
+        cache.insert("old".to_string(), json!(1));
+        sleep(Duration::from_millis(60));
+        cache.insert("a".to_string(), json!(2));
+        cache.insert("b".to_string(), json!(3));
+        assert_eq!(cache.get("a"), Some(json!(2)));
+        assert_eq!(cache.get("b"), Some(json!(3)));
+    }
+
+    #[test]
+    fn zero_ttl_or_capacity_disables_the_cache() {
+        for cache in [
+            ResponseCache::new(Duration::ZERO, 10),
+            ResponseCache::new(Duration::from_secs(60), 0),
+        ] {
+            cache.insert("a".to_string(), json!(1));
+            assert_eq!(cache.get("a"), None);
+        }
+    }
+}
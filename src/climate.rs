@@ -0,0 +1,212 @@
+//! Downscaled climate model projections from the climate API.
+
+use crate::{
+    config::Api,
+    stats::{self, Summary},
+    units::Units,
+    OpenMeteoServer, Place, QueryOptions,
+};
+use chrono::{Datelike, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// CMIP6 HighResMIP models served by the climate API.
+pub const CLIMATE_MODELS: [&str; 7] = [
+    "CMCC_CM2_VHR4",
+    "FGOALS_f3_H",
+    "HiRAM_SIT_HR",
+    "MRI_AGCM3_2_S",
+    "EC_Earth3P_HR",
+    "MPI_ESM1_2_XR",
+    "NICAM16_8S",
+];
+
+pub const DEFAULT_CLIMATE_MODELS: [&str; 3] = ["EC_Earth3P_HR", "MPI_ESM1_2_XR", "MRI_AGCM3_2_S"];
+
+/// Ranges longer than this are summarised by decade only.
+const MAX_ANNUAL_ROWS: i32 = 30;
+
+impl OpenMeteoServer {
+    pub async fn fetch_climate_projection(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start_year: i32,
+        end_year: i32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/climate?latitude={}&longitude={}&start_date={}-01-01&end_date={}-12-31&daily=temperature_2m_mean,temperature_2m_max,temperature_2m_min,precipitation_sum{}",
+            self.base_url(Api::Climate),
+            latitude,
+            longitude,
+            start_year,
+            end_year,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Climate API").await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn format_climate_projection(
+        &self,
+        data: &Value,
+        place: &Place,
+        models: &[String],
+        start_year: i32,
+        end_year: i32,
+        units: Units,
+    ) -> String {
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let dates: Vec<Option<NaiveDate>> = daily
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec)
+            .iter()
+            .map(|v| v.as_str().and_then(|d| self.validate_date(d).ok()))
+            .collect();
+
+        let mut projection = format!(
+            "🌍 Climate Projection (CMIP6)\nLocation: {}{}\nPeriod: {} to {}\n",
+            place,
+            self.format_timezone(data),
+            start_year,
+            end_year
+        );
+
+        let fmt_value = |value: Option<f64>, precision: usize, unit: &str| match value {
+            Some(v) => format!("{:.*}{}", precision, v, unit),
+            None => "n/a".to_string(),
+        };
+        let total = |summary: &Summary| (!summary.is_empty()).then_some(summary.sum);
+
+        for model in models {
+            // With several models the API suffixes every variable with the model
+            // name; a missing suffixed series must not fall back to another model's
+            let key = |variable: &str| {
+                let suffixed = format!("{}_{}", variable, model);
+                if models.len() == 1 && daily.get(&suffixed).is_none() {
+                    variable.to_string()
+                } else {
+                    suffixed
+                }
+            };
+            let series = |variable: &str| {
+                daily
+                    .get(key(variable))
+                    .and_then(|v| v.as_array())
+                    .unwrap_or(&empty_vec)
+            };
+            let unit = |variable: &str| {
+                daily_units
+                    .get(key(variable))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(units.label_for(variable))
+                    .to_string()
+            };
+
+            let temp_mean = series("temperature_2m_mean");
+            let temp_max = series("temperature_2m_max");
+            let temp_min = series("temperature_2m_min");
+            let precipitation = series("precipitation_sum");
+            let temp_unit = unit("temperature_2m_mean");
+            let precip_unit = unit("precipitation_sum");
+
+            // (mean, max, min, precipitation) per year
+            let mut annual: BTreeMap<i32, [Summary; 4]> = BTreeMap::new();
+            let mut seasonal_temp = [Summary::default(); 4];
+            let mut seasonal_precip = [Summary::default(); 4];
+
+            for (i, date) in dates.iter().enumerate() {
+                let Some(date) = date else {
+                    continue;
+                };
+                let at = |values: &Vec<Value>| values.get(i).and_then(|v| v.as_f64());
+                let year = annual.entry(date.year()).or_default();
+                let season = stats::season_index(date.month());
+
+                if let Some(t) = at(temp_mean) {
+                    year[0].push(t);
+                    seasonal_temp[season].push(t);
+                }
+                if let Some(t) = at(temp_max) {
+                    year[1].push(t);
+                }
+                if let Some(t) = at(temp_min) {
+                    year[2].push(t);
+                }
+                if let Some(p) = at(precipitation) {
+                    year[3].push(p);
+                    seasonal_precip[season].push(p);
+                }
+            }
+
+            annual.retain(|_, year| !year[0].is_empty());
+            projection.push_str(&format!("\n🧪 Model: {}\n", model));
+            if annual.is_empty() {
+                projection.push_str("No data returned for this model.\n");
+                continue;
+            }
+
+            if end_year - start_year < MAX_ANNUAL_ROWS {
+                projection.push_str("📅 Annual:\n");
+                for (year, [mean, max, min, precip]) in &annual {
+                    projection.push_str(&format!(
+                        "{}: mean {} (avg high {}, avg low {}), precipitation {}\n",
+                        year,
+                        fmt_value(mean.mean(), 1, &temp_unit),
+                        fmt_value(max.mean(), 1, &temp_unit),
+                        fmt_value(min.mean(), 1, &temp_unit),
+                        fmt_value(total(precip), 0, &precip_unit),
+                    ));
+                }
+            }
+
+            let mut decades: BTreeMap<i32, (Summary, Summary)> = BTreeMap::new();
+            for (year, [mean, _, _, precip]) in &annual {
+                let decade = decades.entry(year - year.rem_euclid(10)).or_default();
+                if let Some(t) = mean.mean() {
+                    decade.0.push(t);
+                }
+                if let Some(p) = total(precip) {
+                    decade.1.push(p);
+                }
+            }
+            projection.push_str("📊 Decades:\n");
+            for (decade, (temp, precip)) in &decades {
+                projection.push_str(&format!(
+                    "{}s: mean {}, precipitation {}/year ({} years)\n",
+                    decade,
+                    fmt_value(temp.mean(), 1, &temp_unit),
+                    fmt_value(precip.mean(), 0, &precip_unit),
+                    temp.count
+                ));
+            }
+
+            let years = annual.len() as f64;
+            projection.push_str("🍂 Seasons (period average):\n");
+            for (season, name) in stats::SEASONS.iter().enumerate() {
+                let Some(mean) = seasonal_temp[season].mean() else {
+                    continue;
+                };
+                projection.push_str(&format!(
+                    "{}: mean {:.1}{}, precipitation {}\n",
+                    name,
+                    mean,
+                    temp_unit,
+                    fmt_value(
+                        total(&seasonal_precip[season]).map(|p| p / years),
+                        0,
+                        &precip_unit
+                    )
+                ));
+            }
+        }
+
+        projection
+    }
+}
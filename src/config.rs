@@ -0,0 +1,241 @@
+//! Command-line options. Every option can also be set through the environment
+//! variable named in its help text.
+
+use std::net::IpAddr;
+use std::path::PathBuf;
+
+use clap::{Args, Parser, ValueEnum};
+
+use crate::units::Units;
+
+/// How MCP messages reach the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Transport {
+    /// One client per process over standard input and output.
+    Stdio,
+    /// Shared HTTP server using Server-Sent Events.
+    Sse,
+}
+
+#[derive(Debug, Clone, Parser)]
+#[command(version, about)]
+pub struct Config {
+    /// Transport to serve MCP over
+    #[arg(long, env = "MCP_TRANSPORT", value_enum, default_value_t = Transport::Stdio)]
+    pub transport: Transport,
+
+    /// Bind address of the SSE transport
+    #[arg(long, env = "MCP_HOST", default_value = "127.0.0.1")]
+    pub host: IpAddr,
+
+    /// Port of the SSE transport
+    #[arg(long, env = "MCP_PORT", default_value_t = 8000)]
+    pub port: u16,
+
+    /// Port of the health check of the SSE transport
+    #[arg(long, env = "MCP_HEALTH_PORT", default_value_t = 8001)]
+    pub health_port: u16,
+
+    /// Log filter, e.g. 'info', 'debug', or 'mcp_server_openmeteo=debug,warn'
+    #[arg(long, env = "RUST_LOG", default_value = "info")]
+    pub log_level: String,
+
+    /// Timeout of each API request in seconds
+    #[arg(long, env = "OPENMETEO_TIMEOUT", default_value_t = 30)]
+    pub timeout: u64,
+
+    #[command(flatten)]
+    pub urls: BaseUrls,
+
+    /// Open-Meteo commercial API key; requests then go to the customer- hosts
+    #[arg(long, env = "OPENMETEO_API_KEY", hide_env_values = true, value_parser = clap::builder::NonEmptyStringValueParser::new())]
+    pub api_key: Option<String>,
+
+    /// Unit preset used when a tool call gives none: 'metric' or 'imperial'
+    #[arg(long, env = "OPENMETEO_UNITS", default_value = "metric", value_parser = Units::preset)]
+    pub units: Units,
+
+    /// Forecast days of get_weather_forecast when a call gives none (1-16)
+    #[arg(long, env = "OPENMETEO_FORECAST_DAYS", default_value_t = 7, value_parser = clap::value_parser!(u32).range(1..=16))]
+    pub forecast_days: u32,
+
+    /// Seconds API responses are cached; 0 disables the cache
+    #[arg(long, env = "OPENMETEO_CACHE_TTL", default_value_t = 0)]
+    pub cache_ttl: u64,
+
+    /// Maximum number of cached API responses
+    #[arg(long, env = "OPENMETEO_CACHE_SIZE", default_value_t = 256)]
+    pub cache_size: usize,
+
+    /// GeoNames cities file for offline reverse geocoding
+    #[arg(long, env = "GEONAMES_CITIES_FILE")]
+    pub geonames_file: Option<PathBuf>,
+}
+
+/// The Open-Meteo APIs, each served from its own host.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Api {
+    /// Forecast API, also serving elevation.
+    Forecast,
+    Archive,
+    Geocoding,
+    AirQuality,
+    Marine,
+    Flood,
+    Ensemble,
+    Climate,
+    Seasonal,
+    HistoricalForecast,
+    PreviousRuns,
+}
+
+impl Api {
+    /// Host of the public API.
+    pub fn host(self) -> &'static str {
+        match self {
+            Self::Forecast => "api.open-meteo.com",
+            Self::Archive => "archive-api.open-meteo.com",
+            Self::Geocoding => "geocoding-api.open-meteo.com",
+            Self::AirQuality => "air-quality-api.open-meteo.com",
+            Self::Marine => "marine-api.open-meteo.com",
+            Self::Flood => "flood-api.open-meteo.com",
+            Self::Ensemble => "ensemble-api.open-meteo.com",
+            Self::Climate => "climate-api.open-meteo.com",
+            Self::Seasonal => "seasonal-api.open-meteo.com",
+            Self::HistoricalForecast => "historical-forecast-api.open-meteo.com",
+            Self::PreviousRuns => "previous-runs-api.open-meteo.com",
+        }
+    }
+}
+
+/// Base URL overrides, e.g. for a self-hosted Open-Meteo instance or a test
+/// stub. APIs without an override use `--base-url`, then the public host or,
+/// for commercial customers, its `customer-` counterpart.
+#[derive(Debug, Clone, Args)]
+pub struct BaseUrls {
+    /// Base URL for every API without its own override below
+    #[arg(long = "base-url", value_name = "URL", env = "OPENMETEO_BASE_URL", value_parser = base_url)]
+    pub all: Option<String>,
+
+    /// Base URL of the forecast and elevation APIs [default: https://api.open-meteo.com]
+    #[arg(long = "forecast-url", value_name = "URL", env = "OPENMETEO_FORECAST_URL", value_parser = base_url)]
+    pub forecast: Option<String>,
+
+    /// Base URL of the historical weather API [default: https://archive-api.open-meteo.com]
+    #[arg(long = "archive-url", value_name = "URL", env = "OPENMETEO_ARCHIVE_URL", value_parser = base_url)]
+    pub archive: Option<String>,
+
+    /// Base URL of the geocoding API [default: https://geocoding-api.open-meteo.com]
+    #[arg(long = "geocoding-url", value_name = "URL", env = "OPENMETEO_GEOCODING_URL", value_parser = base_url)]
+    pub geocoding: Option<String>,
+
+    /// Base URL of the air quality API [default: https://air-quality-api.open-meteo.com]
+    #[arg(long = "air-quality-url", value_name = "URL", env = "OPENMETEO_AIR_QUALITY_URL", value_parser = base_url)]
+    pub air_quality: Option<String>,
+
+    /// Base URL of the marine API [default: https://marine-api.open-meteo.com]
+    #[arg(long = "marine-url", value_name = "URL", env = "OPENMETEO_MARINE_URL", value_parser = base_url)]
+    pub marine: Option<String>,
+
+    /// Base URL of the flood API [default: https://flood-api.open-meteo.com]
+    #[arg(long = "flood-url", value_name = "URL", env = "OPENMETEO_FLOOD_URL", value_parser = base_url)]
+    pub flood: Option<String>,
+
+    /// Base URL of the ensemble API [default: https://ensemble-api.open-meteo.com]
+    #[arg(long = "ensemble-url", value_name = "URL", env = "OPENMETEO_ENSEMBLE_URL", value_parser = base_url)]
+    pub ensemble: Option<String>,
+
+    /// Base URL of the climate API [default: https://climate-api.open-meteo.com]
+    #[arg(long = "climate-url", value_name = "URL", env = "OPENMETEO_CLIMATE_URL", value_parser = base_url)]
+    pub climate: Option<String>,
+
+    /// Base URL of the seasonal forecast API [default: https://seasonal-api.open-meteo.com]
+    #[arg(long = "seasonal-url", value_name = "URL", env = "OPENMETEO_SEASONAL_URL", value_parser = base_url)]
+    pub seasonal: Option<String>,
+
+    /// Base URL of the historical forecast API [default: https://historical-forecast-api.open-meteo.com]
+    #[arg(long = "historical-forecast-url", value_name = "URL", env = "OPENMETEO_HISTORICAL_FORECAST_URL", value_parser = base_url)]
+    pub historical_forecast: Option<String>,
+
+    /// Base URL of the previous model runs API [default: https://previous-runs-api.open-meteo.com]
+    #[arg(long = "previous-runs-url", value_name = "URL", env = "OPENMETEO_PREVIOUS_RUNS_URL", value_parser = base_url)]
+    pub previous_runs: Option<String>,
+}
+
+impl BaseUrls {
+    /// Base URL requests to `api` go to.
+    pub fn get(&self, api: Api, customer: bool) -> String {
+        let specific = match api {
+            Api::Forecast => &self.forecast,
+            Api::Archive => &self.archive,
+            Api::Geocoding => &self.geocoding,
+            Api::AirQuality => &self.air_quality,
+            Api::Marine => &self.marine,
+            Api::Flood => &self.flood,
+            Api::Ensemble => &self.ensemble,
+            Api::Climate => &self.climate,
+            Api::Seasonal => &self.seasonal,
+            Api::HistoricalForecast => &self.historical_forecast,
+            Api::PreviousRuns => &self.previous_runs,
+        };
+        specific
+            .as_ref()
+            .or(self.all.as_ref())
+            .cloned()
+            .unwrap_or_else(|| match customer {
+                true => format!("https://customer-{}", api.host()),
+                false => format!("https://{}", api.host()),
+            })
+    }
+}
+
+/// An http(s) URL without a trailing slash, so that paths can be appended.
+fn base_url(url: &str) -> Result<String, String> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(url.trim_end_matches('/').to_string())
+    } else {
+        Err(format!(
+            "Invalid base URL: '{}'. Must start with http:// or https://.",
+            url
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(args: &[&str]) -> BaseUrls {
+        Config::parse_from(["mcp-server-openmeteo"].iter().chain(args)).urls
+    }
+
+    #[test]
+    fn base_urls_prefer_the_api_override_then_the_shared_url() {
+        let urls = urls(&[
+            "--base-url",
+            "http://localhost:8080/",
+            "--archive-url",
+            "http://archive.local",
+        ]);
+        assert_eq!(urls.get(Api::Archive, true), "http://archive.local");
+        assert_eq!(urls.get(Api::Forecast, true), "http://localhost:8080");
+        assert_eq!(urls.get(Api::Marine, false), "http://localhost:8080");
+    }
+
+    #[test]
+    fn base_urls_default_to_the_public_or_customer_host() {
+        let urls = urls(&[]);
+        assert_eq!(
+            urls.get(Api::Archive, false),
+            "https://archive-api.open-meteo.com"
+        );
+        assert_eq!(
+            urls.get(Api::Archive, true),
+            "https://customer-archive-api.open-meteo.com"
+        );
+        assert_eq!(
+            base_url("localhost:8080").unwrap_err(),
+            "Invalid base URL: 'localhost:8080'. Must start with http:// or https://."
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Terrain elevation from the elevation API.
+
+use crate::{config::Api, Coordinates, OpenMeteoServer};
+use serde_json::Value;
+
+impl OpenMeteoServer {
+    pub async fn fetch_elevation(&self, locations: &[Coordinates]) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/elevation?{}",
+            self.base_url(Api::Forecast),
+            Coordinates::to_query(locations)
+        );
+
+        self.get_json(&url, "OpenMeteo Elevation API").await
+    }
+
+    pub fn format_elevations(&self, data: &Value, locations: &[Coordinates]) -> String {
+        let empty_vec = vec![];
+        let elevations = data
+            .get("elevation")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let mut report = "⛰️ Elevation (90m digital elevation model)\n\n".to_string();
+        for (i, location) in locations.iter().enumerate() {
+            let elevation = match elevations.get(i).and_then(|v| v.as_f64()) {
+                Some(elevation) => format!("{:.0}m", elevation),
+                None => "Unknown".to_string(),
+            };
+            report.push_str(&format!(
+                "📍 {:.4}°, {:.4}°: {}\n",
+                location.latitude, location.longitude, elevation
+            ));
+        }
+
+        report
+    }
+}
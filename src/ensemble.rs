@@ -0,0 +1,200 @@
+//! Ensemble member spread from the ensemble API.
+
+use crate::{
+    config::Api,
+    stats::{self, Summary},
+    units::Units,
+    OpenMeteoServer, Place, QueryOptions,
+};
+use serde_json::Value;
+
+/// Ensemble models as (tool name, Open-Meteo model name, display label).
+pub const ENSEMBLE_MODELS: [(&str, &str, &str); 4] = [
+    ("icon_eps", "icon_seamless", "ICON-EPS"),
+    ("gfs_ensemble", "gfs_seamless", "GFS Ensemble"),
+    ("ecmwf_ifs_ensemble", "ecmwf_ifs025", "ECMWF IFS Ensemble"),
+    ("gem_ensemble", "gem_global", "GEM Global Ensemble"),
+];
+
+/// Daily quantity derived from the hourly ensemble members.
+#[derive(Clone, Copy)]
+pub enum EnsembleVariable {
+    Precipitation,
+    TemperatureMax,
+    TemperatureMin,
+    WindSpeedMax,
+}
+
+impl EnsembleVariable {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name {
+            "precipitation" => Some(Self::Precipitation),
+            "temperature_max" => Some(Self::TemperatureMax),
+            "temperature_min" => Some(Self::TemperatureMin),
+            "wind_speed_max" => Some(Self::WindSpeedMax),
+            _ => None,
+        }
+    }
+
+    fn hourly_variable(self) -> &'static str {
+        match self {
+            Self::Precipitation => "precipitation",
+            Self::TemperatureMax | Self::TemperatureMin => "temperature_2m",
+            Self::WindSpeedMax => "wind_speed_10m",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Precipitation => "Daily precipitation sum",
+            Self::TemperatureMax => "Daily maximum temperature",
+            Self::TemperatureMin => "Daily minimum temperature",
+            Self::WindSpeedMax => "Daily maximum wind speed",
+        }
+    }
+
+    /// Reduces one member's hourly values for a day to the daily quantity.
+    fn aggregate(self, values: &[f64]) -> Option<f64> {
+        if values.is_empty() {
+            return None;
+        }
+        Some(match self {
+            Self::Precipitation => values.iter().sum(),
+            Self::TemperatureMax | Self::WindSpeedMax => {
+                values.iter().copied().fold(f64::MIN, f64::max)
+            }
+            Self::TemperatureMin => values.iter().copied().fold(f64::MAX, f64::min),
+        })
+    }
+}
+
+impl OpenMeteoServer {
+    pub async fn fetch_ensemble_forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        variable: EnsembleVariable,
+        days: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/ensemble?latitude={}&longitude={}&hourly={}&forecast_days={}{}",
+            self.base_url(Api::Ensemble),
+            latitude,
+            longitude,
+            variable.hourly_variable(),
+            days,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Ensemble API").await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn format_ensemble_forecast(
+        &self,
+        data: &Value,
+        place: &Place,
+        model_label: &str,
+        variable: EnsembleVariable,
+        threshold: Option<f64>,
+        units: Units,
+    ) -> String {
+        let hourly = data.get("hourly").unwrap_or(&Value::Null);
+        let hourly_units = data.get("hourly_units").unwrap_or(&Value::Null);
+        let base = variable.hourly_variable();
+        let member_prefix = format!("{}_member", base);
+
+        let empty_vec = vec![];
+        let times = hourly
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let unit = hourly_units
+            .get(base)
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.label_for(base));
+
+        // The control run is keyed by the bare variable name, perturbed members
+        // by "<variable>_memberNN".
+        let members: Vec<&Vec<Value>> = hourly
+            .as_object()
+            .map(|block| {
+                block
+                    .iter()
+                    .filter(|(key, _)| *key == base || key.starts_with(&member_prefix))
+                    .filter_map(|(_, values)| values.as_array())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut days: Vec<&str> = Vec::new();
+        for time in times {
+            let day = time
+                .as_str()
+                .and_then(|t| t.split_once('T'))
+                .map(|(day, _)| day)
+                .unwrap_or("Unknown");
+            if days.last() != Some(&day) {
+                days.push(day);
+            }
+        }
+
+        let mut forecast = format!(
+            "🎲 Ensemble Forecast ({}, {} members)\nLocation: {}{}\nVariable: {} ({})\n",
+            model_label,
+            members.len(),
+            place,
+            self.format_timezone(data),
+            variable.label(),
+            unit
+        );
+        if let Some(threshold) = threshold {
+            forecast.push_str(&format!("Threshold: > {}{}\n", threshold, unit));
+        }
+        forecast.push('\n');
+
+        for day in days {
+            let indices: Vec<usize> = times
+                .iter()
+                .enumerate()
+                .filter(|(_, time)| time.as_str().is_some_and(|t| t.starts_with(day)))
+                .map(|(i, _)| i)
+                .collect();
+
+            let mut daily_values: Vec<f64> = members
+                .iter()
+                .filter_map(|values| {
+                    let hours: Vec<f64> = indices
+                        .iter()
+                        .filter_map(|&i| values.get(i).and_then(|v| v.as_f64()))
+                        .collect();
+                    variable.aggregate(&hours)
+                })
+                .collect();
+
+            let Some(median) = stats::median(&mut daily_values) else {
+                forecast.push_str(&format!("📅 {}: no member data\n", day));
+                continue;
+            };
+            let spread: Summary = daily_values.iter().copied().collect();
+
+            forecast.push_str(&format!(
+                "📅 {}: min {:.1}{} | median {:.1}{} | max {:.1}{}",
+                day, spread.min, unit, median, unit, spread.max, unit
+            ));
+            if let Some(threshold) = threshold {
+                let exceeding = daily_values.iter().filter(|v| **v > threshold).count();
+                forecast.push_str(&format!(
+                    " | P(> {}{}): {:.0}%",
+                    threshold,
+                    unit,
+                    exceeding as f64 * 100.0 / spread.count as f64
+                ));
+            }
+            forecast.push('\n');
+        }
+
+        forecast
+    }
+}
@@ -0,0 +1,145 @@
+//! River discharge from the flood API.
+
+use crate::{config::Api, OpenMeteoServer, Place, QueryOptions};
+use serde_json::Value;
+
+/// Days whose discharge exceeds the ensemble median by this factor are flagged.
+const DISCHARGE_ALERT_RATIO: f64 = 1.5;
+
+/// Time window requested from the flood API.
+pub enum DischargeWindow {
+    Relative {
+        past_days: u32,
+        forecast_days: u32,
+    },
+    Dates {
+        start_date: String,
+        end_date: String,
+    },
+}
+
+impl OpenMeteoServer {
+    pub async fn fetch_river_discharge(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        window: &DischargeWindow,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let window = match window {
+            DischargeWindow::Relative {
+                past_days,
+                forecast_days,
+            } => format!("past_days={}&forecast_days={}", past_days, forecast_days),
+            DischargeWindow::Dates {
+                start_date,
+                end_date,
+            } => format!("start_date={}&end_date={}", start_date, end_date),
+        };
+        let url = format!(
+            "{}/v1/flood?latitude={}&longitude={}&daily=river_discharge,river_discharge_mean,river_discharge_median,river_discharge_max,river_discharge_p25,river_discharge_p75&{}{}",
+            self.base_url(Api::Flood),
+            latitude,
+            longitude,
+            window,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Flood API").await
+    }
+
+    pub fn format_river_discharge(&self, data: &Value, place: &Place) -> String {
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let series = |key: &str| {
+            daily
+                .get(key)
+                .and_then(|v| v.as_array())
+                .unwrap_or(&empty_vec)
+        };
+        let dates = series("time");
+        let discharge = series("river_discharge");
+        let mean = series("river_discharge_mean");
+        let median = series("river_discharge_median");
+        let max = series("river_discharge_max");
+        let p25 = series("river_discharge_p25");
+        let p75 = series("river_discharge_p75");
+
+        let unit = daily_units
+            .get("river_discharge")
+            .and_then(|v| v.as_str())
+            .unwrap_or("m³/s");
+
+        let fmt_value = |value: Option<f64>| match value {
+            Some(v) => format!("{:.1}", v),
+            None => "n/a".to_string(),
+        };
+
+        // Dates are in the response's timezone, so "today" must be too.
+        let today = self.local_today(data).format("%Y-%m-%d").to_string();
+
+        let mut report = format!(
+            "🏞️ River Discharge\nLocation: {}{}\nUnit: {}\n",
+            place,
+            self.format_timezone(data),
+            unit
+        );
+
+        let mut section = "";
+        let mut flagged = Vec::new();
+        for (i, date) in dates.iter().enumerate() {
+            let date = date.as_str().unwrap_or("Unknown");
+            let heading = if date < today.as_str() {
+                "\n⏪ Past\n"
+            } else {
+                "\n⏩ Forecast\n"
+            };
+            if heading != section {
+                report.push_str(heading);
+                section = heading;
+            }
+
+            let at = |values: &Vec<Value>| values.get(i).and_then(|v| v.as_f64());
+            let value = at(discharge).or(at(mean));
+            let day_median = at(median);
+
+            let alert = match (value, day_median) {
+                (Some(v), Some(m)) if m > 0.0 && v / m >= DISCHARGE_ALERT_RATIO => {
+                    flagged.push(date);
+                    format!(" ⚠️ {:.1}× median", v / m)
+                }
+                _ => String::new(),
+            };
+
+            report.push_str(&format!(
+                "📅 {}: {} (mean {}, median {}, max {}, p25–p75 {}–{}){}\n",
+                date,
+                fmt_value(value),
+                fmt_value(at(mean)),
+                fmt_value(day_median),
+                fmt_value(at(max)),
+                fmt_value(at(p25)),
+                fmt_value(at(p75)),
+                alert
+            ));
+        }
+
+        if flagged.is_empty() {
+            report.push_str(&format!(
+                "\n✅ No days exceed {:.1}× the ensemble median.\n",
+                DISCHARGE_ALERT_RATIO
+            ));
+        } else {
+            report.push_str(&format!(
+                "\n⚠️ {} day(s) exceed {:.1}× the ensemble median: {}\n",
+                flagged.len(),
+                DISCHARGE_ALERT_RATIO,
+                flagged.join(", ")
+            ));
+        }
+
+        report
+    }
+}
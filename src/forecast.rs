@@ -0,0 +1,570 @@
+//! Current, daily, and hourly weather from the forecast API.
+
+use crate::{
+    config::Api,
+    stats::Summary,
+    units::Units,
+    variables::{self, Block},
+    Coordinates, OpenMeteoServer, Place, QueryOptions,
+};
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
+/// Variables requested by `get_current_weather`, shown by `format_current_weather`.
+const CURRENT_WEATHER_VARIABLES: &[&str] = &[
+    "temperature_2m",
+    "relative_humidity_2m",
+    "apparent_temperature",
+    "is_day",
+    "precipitation",
+    "rain",
+    "showers",
+    "snowfall",
+    "weather_code",
+    "cloud_cover",
+    "pressure_msl",
+    "surface_pressure",
+    "wind_speed_10m",
+    "wind_direction_10m",
+    "wind_gusts_10m",
+];
+
+/// Daily variables requested by `get_weather_forecast`.
+const FORECAST_DAILY_VARIABLES: &[&str] = &[
+    "weather_code",
+    "temperature_2m_max",
+    "temperature_2m_min",
+    "apparent_temperature_max",
+    "apparent_temperature_min",
+    "sunrise",
+    "sunset",
+    "daylight_duration",
+    "sunshine_duration",
+    "uv_index_max",
+    "precipitation_sum",
+    "rain_sum",
+    "showers_sum",
+    "snowfall_sum",
+    "precipitation_hours",
+    "precipitation_probability_max",
+    "wind_speed_10m_max",
+    "wind_gusts_10m_max",
+    "wind_direction_10m_dominant",
+    "shortwave_radiation_sum",
+];
+
+impl OpenMeteoServer {
+    pub async fn fetch_current_weather(
+        &self,
+        locations: &[Coordinates],
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/forecast?{}{}{}",
+            self.base_url(Api::Forecast),
+            Coordinates::to_query(locations),
+            variables::query(
+                Block::Current,
+                CURRENT_WEATHER_VARIABLES,
+                &options.variables
+            ),
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo API").await
+    }
+
+    pub async fn fetch_weather_forecast(
+        &self,
+        locations: &[Coordinates],
+        days: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/forecast?{}{}{}&forecast_days={}{}",
+            self.base_url(Api::Forecast),
+            Coordinates::to_query(locations),
+            variables::query(Block::Daily, FORECAST_DAILY_VARIABLES, &options.variables),
+            variables::query(Block::Hourly, &[], &options.variables),
+            days,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo API").await
+    }
+
+    pub async fn fetch_hourly_forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        hours: u32,
+        start_time: Option<NaiveDateTime>,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let window = match start_time {
+            Some(start) => {
+                let end = start + chrono::Duration::hours(i64::from(hours) - 1);
+                format!(
+                    "start_hour={}&end_hour={}",
+                    start.format("%Y-%m-%dT%H:%M"),
+                    end.format("%Y-%m-%dT%H:%M")
+                )
+            }
+            None => format!("forecast_hours={}", hours),
+        };
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&hourly=temperature_2m,precipitation_probability,precipitation,weather_code,is_day,wind_speed_10m,wind_direction_10m,wind_gusts_10m,cloud_cover,visibility&{}{}",
+            self.base_url(Api::Forecast),
+            latitude,
+            longitude,
+            window,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo API").await
+    }
+
+    pub fn format_current_weather(&self, data: &Value, place: &Place, units: Units) -> String {
+        let current = data.get("current").unwrap_or(&Value::Null);
+        let current_units = data.get("current_units").unwrap_or(&Value::Null);
+
+        let temperature = current
+            .get("temperature_2m")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let temp_unit = current_units
+            .get("temperature_2m")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.temperature.label());
+
+        let humidity = current
+            .get("relative_humidity_2m")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let humidity_unit = current_units
+            .get("relative_humidity_2m")
+            .and_then(|v| v.as_str())
+            .unwrap_or("%");
+
+        let feels_like = current
+            .get("apparent_temperature")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let precipitation = current
+            .get("precipitation")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let precip_unit = current_units
+            .get("precipitation")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.precipitation.label());
+
+        let wind_speed = current
+            .get("wind_speed_10m")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let wind_unit = current_units
+            .get("wind_speed_10m")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.wind_speed.label());
+        let wind_direction = current
+            .get("wind_direction_10m")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+
+        let pressure = current
+            .get("pressure_msl")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let pressure_unit = current_units
+            .get("pressure_msl")
+            .and_then(|v| v.as_str())
+            .unwrap_or("hPa");
+
+        let cloud_cover = current
+            .get("cloud_cover")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let weather_code = current
+            .get("weather_code")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        let is_day = current.get("is_day").and_then(|v| v.as_u64()).unwrap_or(0) == 1;
+
+        let time = current
+            .get("time")
+            .and_then(|v| v.as_str())
+            .unwrap_or("Unknown");
+
+        let weather_description = self.get_weather_description(weather_code, is_day);
+
+        format!(
+            "🌍 Current Weather\nLocation: {}{}{}{}\nTime: {}\n\n🌡️ Temperature: {:.1}{}\n🤔 Feels like: {:.1}{}\n💧 Humidity: {:.0}{}\n☔ Precipitation: {:.1}{}\n💨 Wind: {:.1}{} from {}°\n🌫️ Cloud cover: {:.0}%\n📊 Pressure: {:.1}{}\n☀️ Conditions: {}",
+            place, self.format_nearest_place(place.latitude, place.longitude),
+            self.format_elevation(data), self.format_timezone(data), time,
+            temperature, temp_unit,
+            feels_like, temp_unit,
+            humidity, humidity_unit,
+            precipitation, precip_unit,
+            wind_speed, wind_unit, wind_direction,
+            cloud_cover,
+            pressure, pressure_unit,
+            weather_description
+        )
+    }
+
+    pub fn format_weather_forecast(
+        &self,
+        data: &Value,
+        place: &Place,
+        days: u32,
+        units: Units,
+    ) -> String {
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let dates = daily
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let temp_max = daily
+            .get("temperature_2m_max")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let temp_min = daily
+            .get("temperature_2m_min")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let weather_codes = daily
+            .get("weather_code")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let precipitation = daily
+            .get("precipitation_sum")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let wind_speed = daily
+            .get("wind_speed_10m_max")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let radiation = daily
+            .get("shortwave_radiation_sum")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let temp_unit = daily_units
+            .get("temperature_2m_max")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.temperature.label());
+        let precip_unit = daily_units
+            .get("precipitation_sum")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.precipitation.label());
+        let wind_unit = daily_units
+            .get("wind_speed_10m_max")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.wind_speed.label());
+        let radiation_unit = daily_units
+            .get("shortwave_radiation_sum")
+            .and_then(|v| v.as_str())
+            .unwrap_or("MJ/m²");
+
+        let mut forecast = format!(
+            "🌍 {}-Day Weather Forecast\nLocation: {}{}{}\n\n",
+            days,
+            place,
+            self.format_elevation(data),
+            self.format_timezone(data)
+        );
+
+        for (i, date) in dates.iter().enumerate().take(days as usize) {
+            let date = date.as_str().unwrap_or("Unknown");
+            let max_temp = temp_max.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let min_temp = temp_min.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let code = weather_codes.get(i).and_then(|v| v.as_u64()).unwrap_or(0);
+            let precip = precipitation.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let wind = wind_speed.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let solar = radiation.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+            let weather_desc = self.get_weather_description(code, true); // Assume day for forecast
+
+            forecast.push_str(&format!(
+                "📅 {}\n🌡️ {:.1}{} / {:.1}{}\n☀️ {}\n☔ {:.1}{}\n💨 {:.1}{}\n🔆 {:.1}{}\n\n",
+                date,
+                max_temp,
+                temp_unit,
+                min_temp,
+                temp_unit,
+                weather_desc,
+                precip,
+                precip_unit,
+                wind,
+                wind_unit,
+                solar,
+                radiation_unit
+            ));
+        }
+
+        forecast
+    }
+
+    pub fn format_hourly_forecast(
+        &self,
+        data: &Value,
+        place: &Place,
+        hours: u32,
+        units: Units,
+    ) -> String {
+        let hourly = data.get("hourly").unwrap_or(&Value::Null);
+        let hourly_units = data.get("hourly_units").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let times = hourly
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let temperature = hourly
+            .get("temperature_2m")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let precip_probability = hourly
+            .get("precipitation_probability")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let precipitation = hourly
+            .get("precipitation")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let weather_codes = hourly
+            .get("weather_code")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let is_day = hourly
+            .get("is_day")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let wind_speed = hourly
+            .get("wind_speed_10m")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let wind_direction = hourly
+            .get("wind_direction_10m")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let wind_gusts = hourly
+            .get("wind_gusts_10m")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let cloud_cover = hourly
+            .get("cloud_cover")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let visibility = hourly
+            .get("visibility")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let temp_unit = hourly_units
+            .get("temperature_2m")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.temperature.label());
+        let precip_unit = hourly_units
+            .get("precipitation")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.precipitation.label());
+        let wind_unit = hourly_units
+            .get("wind_speed_10m")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.wind_speed.label());
+        let visibility_unit = hourly_units
+            .get("visibility")
+            .and_then(|v| v.as_str())
+            .unwrap_or("m");
+
+        let mut forecast = format!(
+            "🌍 {}-Hour Weather Forecast\nLocation: {}{}\n",
+            hours,
+            place,
+            self.format_timezone(data)
+        );
+
+        let mut current_day = "";
+        for (i, time) in times.iter().enumerate().take(hours as usize) {
+            let time = time.as_str().unwrap_or("Unknown");
+            let (day, hour) = time.split_once('T').unwrap_or((time, ""));
+            if day != current_day {
+                forecast.push_str(&format!("\n📅 {}\n", day));
+                current_day = day;
+            }
+
+            let temp = temperature.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let probability = precip_probability
+                .get(i)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let precip = precipitation.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let code = weather_codes.get(i).and_then(|v| v.as_u64()).unwrap_or(0);
+            let day_flag = is_day.get(i).and_then(|v| v.as_u64()).unwrap_or(1) == 1;
+            let wind = wind_speed.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let direction = wind_direction
+                .get(i)
+                .and_then(|v| v.as_f64())
+                .unwrap_or(0.0);
+            let gusts = wind_gusts.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let clouds = cloud_cover.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+            let vis = visibility.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+
+            let weather_desc = self.get_weather_description(code, day_flag);
+
+            forecast.push_str(&format!(
+                "🕐 {} | 🌡️ {:.1}{} | ☀️ {} | ☔ {:.0}% ({:.1}{}) | 💨 {:.1}{} from {:.0}° (gusts {:.1}{}) | 🌫️ {:.0}% | 👁️ {:.0}{}\n",
+                hour,
+                temp, temp_unit,
+                weather_desc,
+                probability, precip, precip_unit,
+                wind, wind_unit, direction, gusts, wind_unit,
+                clouds,
+                vis, visibility_unit
+            ));
+        }
+
+        forecast
+    }
+
+    /// Side-by-side view of a multi-model response. Open-Meteo suffixes every
+    /// variable with the model name, e.g. "temperature_2m_max_icon_seamless".
+    #[allow(clippy::too_many_arguments)]
+    pub fn format_model_comparison(
+        &self,
+        data: &Value,
+        title: &str,
+        place: &Place,
+        models: &[String],
+        max_days: usize,
+        units: Units,
+    ) -> String {
+        let mut report = format!(
+            "🌍 {} — Model Comparison\nLocation: {}{}{}\n\n",
+            title,
+            place,
+            self.format_elevation(data),
+            self.format_timezone(data)
+        );
+
+        let lookup = |block: &str, key: &str, model: &str| -> Option<Value> {
+            data.get(block)?.get(format!("{}_{}", key, model)).cloned()
+        };
+        let unit = |block: &str, key: &str, model: &str| -> String {
+            data.get(block)
+                .and_then(|block_units| block_units.get(format!("{}_{}", key, model)))
+                .and_then(|v| v.as_str())
+                .unwrap_or(units.label_for(key))
+                .to_string()
+        };
+        let fmt_value = |value: Option<f64>, unit: &str| match value {
+            Some(v) => format!("{:.1}{}", v, unit),
+            None => "n/a".to_string(),
+        };
+
+        if data.get("current").is_some() {
+            for model in models {
+                let value = |key: &str| lookup("current", key, model).and_then(|v| v.as_f64());
+                let description = lookup("current", "weather_code", model)
+                    .and_then(|v| v.as_u64())
+                    .map(|code| self.get_weather_description(code, true))
+                    .unwrap_or("n/a");
+                report.push_str(&format!(
+                    "🧮 {}: 🌡️ {} | ☔ {} | 💨 {} | 🌫️ {} | ☀️ {}\n",
+                    self.model_label(model),
+                    fmt_value(
+                        value("temperature_2m"),
+                        &unit("current_units", "temperature_2m", model)
+                    ),
+                    fmt_value(
+                        value("precipitation"),
+                        &unit("current_units", "precipitation", model)
+                    ),
+                    fmt_value(
+                        value("wind_speed_10m"),
+                        &unit("current_units", "wind_speed_10m", model)
+                    ),
+                    fmt_value(value("cloud_cover"), "%"),
+                    description
+                ));
+            }
+            return report;
+        }
+
+        let empty_vec = vec![];
+        let dates = data
+            .get("daily")
+            .and_then(|daily| daily.get("time"))
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let series = |key: &str, model: &str| -> Vec<Option<f64>> {
+            lookup("daily", key, model)
+                .and_then(|v| v.as_array().cloned())
+                .unwrap_or_default()
+                .iter()
+                .map(|v| v.as_f64())
+                .collect()
+        };
+
+        report.push_str("📊 Period summary:\n");
+        for model in models {
+            let highs: Summary = series("temperature_2m_max", model)
+                .into_iter()
+                .flatten()
+                .collect();
+            let lows: Summary = series("temperature_2m_min", model)
+                .into_iter()
+                .flatten()
+                .collect();
+            let precip: Summary = series("precipitation_sum", model)
+                .into_iter()
+                .flatten()
+                .collect();
+            let temp_unit = unit("daily_units", "temperature_2m_max", model);
+            report.push_str(&format!(
+                "🧮 {}: avg high {}, avg low {}, total precipitation {}\n",
+                self.model_label(model),
+                fmt_value(highs.mean(), &temp_unit),
+                fmt_value(lows.mean(), &temp_unit),
+                fmt_value(
+                    (!precip.is_empty()).then_some(precip.sum),
+                    &unit("daily_units", "precipitation_sum", model)
+                ),
+            ));
+        }
+
+        for (i, date) in dates.iter().enumerate().take(max_days) {
+            report.push_str(&format!("\n📅 {}\n", date.as_str().unwrap_or("Unknown")));
+            for model in models {
+                let at = |key: &str| series(key, model).get(i).copied().flatten();
+                let temp_unit = unit("daily_units", "temperature_2m_max", model);
+                let description = lookup("daily", "weather_code", model)
+                    .and_then(|codes| codes.get(i).and_then(|v| v.as_u64()))
+                    .map(|code| self.get_weather_description(code, true))
+                    .unwrap_or("n/a");
+                report.push_str(&format!(
+                    "🧮 {}: 🌡️ {} / {} | ☔ {} | 💨 {} | ☀️ {}\n",
+                    self.model_label(model),
+                    fmt_value(at("temperature_2m_max"), &temp_unit),
+                    fmt_value(at("temperature_2m_min"), &temp_unit),
+                    fmt_value(
+                        at("precipitation_sum"),
+                        &unit("daily_units", "precipitation_sum", model)
+                    ),
+                    fmt_value(
+                        at("wind_speed_10m_max"),
+                        &unit("daily_units", "wind_speed_10m_max", model)
+                    ),
+                    description
+                ));
+            }
+        }
+
+        report
+    }
+}
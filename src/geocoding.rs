@@ -0,0 +1,263 @@
+//! Place search and lookup on the geocoding API.
+
+use crate::{config::Api, redact_api_key, OpenMeteoServer};
+use serde_json::Value;
+
+/// Optional query parameters of the geocoding search.
+#[derive(Debug, Default, Clone)]
+pub struct SearchOptions {
+    /// ISO 639-1 code; English when empty.
+    pub language: String,
+    /// ISO 3166-1 alpha-2 code restricting the results to one country.
+    pub country_code: Option<String>,
+}
+
+impl SearchOptions {
+    pub fn to_query(&self) -> String {
+        let mut query = format!(
+            "&language={}",
+            if self.language.is_empty() {
+                "en"
+            } else {
+                &self.language
+            }
+        );
+        if let Some(country_code) = &self.country_code {
+            query.push_str(&format!("&countryCode={}", country_code));
+        }
+        query
+    }
+}
+
+/// GeoNames feature codes shown with a description in search results.
+pub const FEATURE_CODES: [(&str, &str); 16] = [
+    ("PPLC", "capital of a political entity"),
+    ("PPLA", "seat of a first-order administrative division"),
+    ("PPLA2", "seat of a second-order administrative division"),
+    ("PPLA3", "seat of a third-order administrative division"),
+    ("PPLA4", "seat of a fourth-order administrative division"),
+    ("PPL", "populated place"),
+    ("PPLX", "section of populated place"),
+    ("PPLL", "populated locality"),
+    ("ADM1", "first-order administrative division"),
+    ("ADM2", "second-order administrative division"),
+    ("ADM3", "third-order administrative division"),
+    ("ADM4", "fourth-order administrative division"),
+    ("PCLI", "independent political entity"),
+    ("AIRP", "airport"),
+    ("ISL", "island"),
+    ("MT", "mountain"),
+];
+
+/// Postcodes listed per search result before the rest are summarised.
+const MAX_POSTCODES: usize = 5;
+
+impl OpenMeteoServer {
+    pub async fn search_locations_helper(
+        &self,
+        query: &str,
+        limit: u32,
+        options: &SearchOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/search?name={}&count={}{}&format=json",
+            self.base_url(Api::Geocoding),
+            urlencoding::encode(query),
+            limit,
+            options.to_query()
+        );
+        self.fetch_geocoding(&url).await
+    }
+
+    /// Looks up a single place by its geocoding ID.
+    pub async fn get_location_helper(
+        &self,
+        id: u64,
+        options: &SearchOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/get?id={}{}&format=json",
+            self.base_url(Api::Geocoding),
+            id,
+            options.to_query()
+        );
+        self.fetch_geocoding(&url).await
+    }
+
+    async fn fetch_geocoding(&self, url: &str) -> Result<Value, anyhow::Error> {
+        tracing::debug!("Geocoding API URL: {}", url); // Log the URL
+        if let Some(data) = self.cache.get(url) {
+            tracing::debug!("Geocoding cache hit");
+            return Ok(data);
+        }
+
+        let response = self.send(url).await?;
+
+        let status = response.status();
+        tracing::debug!("Geocoding API response status: {}", status);
+
+        if !status.is_success() {
+            let err_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Failed to read error body".to_string());
+            tracing::error!(
+                "Geocoding API non-success. Status: {}. Body: {}",
+                status,
+                err_text
+            );
+            return Err(anyhow::anyhow!(
+                "OpenMeteo Geocoding API error: {}. Body: {}",
+                status,
+                err_text
+            ));
+        }
+
+        let response_text = response.text().await.map_err(|e| redact_api_key(e, url))?;
+        tracing::debug!("Geocoding API response text: {}", response_text);
+
+        let data: Value = serde_json::from_str(&response_text).map_err(|e| {
+            tracing::error!(
+                "Failed to parse Geocoding API JSON. Error: {}. Response text: {}",
+                e,
+                response_text
+            );
+            anyhow::anyhow!(
+                "Failed to parse Geocoding API JSON response: {}. Response text snippet: {:.200}",
+                e,
+                response_text
+            )
+        })?;
+
+        self.cache.insert(url.to_string(), data.clone());
+        Ok(data)
+    }
+
+    pub fn format_locations(
+        &self,
+        data: &Value,
+        min_population: Option<u64>,
+        limit: usize,
+    ) -> String {
+        let empty_vec = vec![];
+        let results: Vec<&Value> = data
+            .get("results")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec)
+            .iter()
+            .filter(|result| match min_population {
+                Some(min) => result
+                    .get("population")
+                    .and_then(|v| v.as_u64())
+                    .is_some_and(|population| population >= min),
+                None => true,
+            })
+            .take(limit)
+            .collect();
+
+        if results.is_empty() {
+            return "No locations found matching your search query.".to_string();
+        }
+
+        let mut locations = "🌍 Location Search Results:\n\n".to_string();
+
+        for (i, result) in results.iter().enumerate() {
+            locations.push_str(&format!("{}. {}\n\n", i + 1, self.format_location(result)));
+        }
+
+        locations
+    }
+
+    /// One geocoding result: name, areas, coordinates, and place details.
+    pub fn format_location(&self, result: &Value) -> String {
+        let text = |key: &str| result.get(key).and_then(|v| v.as_str());
+        let name = text("name").unwrap_or("Unknown");
+        let country = text("country").unwrap_or("Unknown");
+        let latitude = result
+            .get("latitude")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let longitude = result
+            .get("longitude")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0);
+        let timezone = text("timezone").unwrap_or("Unknown");
+        let population = result.get("population").and_then(|v| v.as_u64());
+
+        let admin_info = if let Some(admin) = text("admin1") {
+            format!(", {}", admin)
+        } else {
+            String::new()
+        };
+
+        let country_code = text("country_code")
+            .map(|code| format!(" ({})", code))
+            .unwrap_or_default();
+
+        let subdivisions: Vec<&str> = ["admin2", "admin3", "admin4"]
+            .iter()
+            .filter_map(|key| text(key))
+            .collect();
+        let subdivision_info = if subdivisions.is_empty() {
+            String::new()
+        } else {
+            format!("\n🗺️ Subdivisions: {}", subdivisions.join(" › "))
+        };
+
+        let elevation_info = match result.get("elevation").and_then(|v| v.as_f64()) {
+            Some(elevation) => format!("\n⛰️ Elevation: {:.0}m", elevation),
+            None => String::new(),
+        };
+
+        let feature_info = match text("feature_code") {
+            Some(code) => match FEATURE_CODES.iter().find(|(known, _)| *known == code) {
+                Some((_, description)) => format!("\n🏷️ Feature: {} ({})", code, description),
+                None => format!("\n🏷️ Feature: {}", code),
+            },
+            None => String::new(),
+        };
+
+        let postcodes: Vec<&str> = result
+            .get("postcodes")
+            .and_then(|v| v.as_array())
+            .map(|codes| codes.iter().filter_map(|code| code.as_str()).collect())
+            .unwrap_or_default();
+        let postcode_info = match postcodes.len() {
+            0 => String::new(),
+            n if n > MAX_POSTCODES => format!(
+                "\n📮 Postcodes: {} (+{} more)",
+                postcodes[..MAX_POSTCODES].join(", "),
+                n - MAX_POSTCODES
+            ),
+            _ => format!("\n📮 Postcodes: {}", postcodes.join(", ")),
+        };
+
+        let pop_info = if let Some(pop) = population {
+            format!("\n👥 Population: {}", pop)
+        } else {
+            String::new()
+        };
+
+        let id_info = match result.get("id").and_then(|v| v.as_u64()) {
+            Some(id) => format!("\n🆔 ID: {}", id),
+            None => String::new(),
+        };
+
+        format!(
+            "📍 {}{}, {}{}{}{}\n📍 Coordinates: {:.4}°, {:.4}°{}{}{}\n🕐 Timezone: {}{}",
+            name,
+            admin_info,
+            country,
+            country_code,
+            id_info,
+            subdivision_info,
+            latitude,
+            longitude,
+            elevation_info,
+            feature_info,
+            postcode_info,
+            timezone,
+            pop_info
+        )
+    }
+}
@@ -0,0 +1,267 @@
+//! Offline reverse geocoding over a GeoNames cities dump (e.g. `cities500.txt`
+//! from https://download.geonames.org/export/dump/).
+
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Mean Earth radius in km.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Length of one degree of latitude in km.
+const KM_PER_DEGREE: f64 = EARTH_RADIUS_KM * std::f64::consts::PI / 180.0;
+
+/// One populated place of the dump.
+#[derive(Debug, Clone)]
+pub struct City {
+    pub id: u64,
+    pub name: String,
+    pub latitude: f64,
+    pub longitude: f64,
+    pub feature_code: String,
+    pub country_code: String,
+    pub population: u64,
+    pub timezone: String,
+}
+
+/// Cities bucketed into 1° × 1° cells for nearest-neighbour queries.
+#[derive(Debug, Default)]
+pub struct Cities {
+    cities: Vec<City>,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl Cities {
+    /// Loads a tab-separated GeoNames file. Malformed lines are skipped.
+    pub fn load(path: &Path) -> Result<Self, anyhow::Error> {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read GeoNames file {}: {}", path.display(), e)
+        })?;
+        let mut cities = Self::default();
+        let mut skipped = 0;
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            match parse_line(line) {
+                Some(city) => cities.insert(city),
+                None => skipped += 1,
+            }
+        }
+        if skipped > 0 {
+            tracing::warn!("Skipped {} malformed lines in {}", skipped, path.display());
+        }
+        Ok(cities)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cities.is_empty()
+    }
+
+    fn insert(&mut self, city: City) {
+        let cell = cell_of(city.latitude, city.longitude);
+        self.cells.entry(cell).or_default().push(self.cities.len());
+        self.cities.push(city);
+    }
+
+    /// Up to `limit` cities closest to the point with their distance in km,
+    /// nearest first. Cells are searched in growing rings around the point
+    /// until no unsearched cell can hold a closer city.
+    pub fn nearest(&self, latitude: f64, longitude: f64, limit: usize) -> Vec<(&City, f64)> {
+        if limit == 0 {
+            return Vec::new();
+        }
+        let (row, column) = cell_of(latitude, longitude);
+        let mut found: Vec<(&City, f64)> = Vec::new();
+
+        for ring in 0..=180 {
+            for (d_row, d_column) in ring_offsets(ring) {
+                let cell_row = row + d_row;
+                if !(-90..90).contains(&cell_row) {
+                    continue;
+                }
+                let cell = (cell_row, (column + d_column + 180).rem_euclid(360) - 180);
+                for &index in self.cells.get(&cell).into_iter().flatten() {
+                    let city = &self.cities[index];
+                    let distance = distance_km(latitude, longitude, city.latitude, city.longitude);
+                    found.push((city, distance));
+                }
+            }
+            found.sort_by(|a, b| a.1.total_cmp(&b.1));
+            found.truncate(limit);
+
+            // Any city outside the searched square is at least `ring` degrees
+            // away in latitude or longitude; a degree of longitude shrinks
+            // towards the poles.
+            let widest = (latitude.abs() + ring as f64 + 1.0).min(90.0);
+            let bound = (ring as f64 * KM_PER_DEGREE).min(
+                2.0 * EARTH_RADIUS_KM
+                    * widest.to_radians().cos()
+                    * (ring as f64 / 2.0).to_radians().sin(),
+            );
+            if found.len() == limit && found[limit - 1].1 <= bound {
+                break;
+            }
+        }
+        found
+    }
+}
+
+/// Great-circle distance between two points in km (haversine formula).
+pub fn distance_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = phi2 - phi1;
+    let d_lambda = (lon2 - lon1).to_radians();
+    let a = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Initial bearing from the first point to the second in degrees (0 = north).
+pub fn bearing(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_lambda = (lon2 - lon1).to_radians();
+    let y = d_lambda.sin() * phi2.cos();
+    let x = phi1.cos() * phi2.sin() - phi1.sin() * phi2.cos() * d_lambda.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+fn cell_of(latitude: f64, longitude: f64) -> (i32, i32) {
+    let row = (latitude.floor() as i32).clamp(-90, 89);
+    let column = (longitude.floor() as i32 + 180).rem_euclid(360) - 180;
+    (row, column)
+}
+
+/// Cell offsets on the border of the square `ring` cells around the centre.
+/// Columns wrap around the antimeridian, so the widest ring stops short of
+/// visiting the opposite column twice.
+fn ring_offsets(ring: i32) -> Vec<(i32, i32)> {
+    if ring == 0 {
+        return vec![(0, 0)];
+    }
+    let columns = |range: std::ops::RangeInclusive<i32>| {
+        range.filter(move |d_column| !(ring >= 180 && *d_column == ring))
+    };
+    let mut offsets = Vec::new();
+    for d_column in columns(-ring..=ring) {
+        offsets.push((-ring, d_column));
+        offsets.push((ring, d_column));
+    }
+    for d_row in -ring + 1..ring {
+        offsets.push((d_row, -ring));
+        if ring < 180 {
+            offsets.push((d_row, ring));
+        }
+    }
+    offsets
+}
+
+/// Columns of the GeoNames "geoname" table: id, name, asciiname,
+/// alternatenames, latitude, longitude, feature class, feature code, country
+/// code, cc2, admin1-4 codes, population, elevation, dem, timezone, date.
+fn parse_line(line: &str) -> Option<City> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    if fields.len() < 18 {
+        return None;
+    }
+    Some(City {
+        id: fields[0].parse().ok()?,
+        name: fields[1].to_string(),
+        latitude: fields[4].parse().ok()?,
+        longitude: fields[5].parse().ok()?,
+        feature_code: fields[7].to_string(),
+        country_code: fields[8].to_string(),
+        population: fields[14].parse().unwrap_or(0),
+        timezone: fields[17].to_string(),
+    })
+}
+
+/// Eight-point compass direction of a bearing in degrees.
+pub fn compass_point(bearing: f64) -> &'static str {
+    const POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+    POINTS[((bearing.rem_euclid(360.0) + 22.5) / 45.0) as usize % 8]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A "geoname" table row with the columns `parse_line` reads.
+    fn line(id: u64, name: &str, latitude: f64, longitude: f64) -> String {
+        format!(
+            "{id}\t{name}\t{name}\t\t{latitude}\t{longitude}\tP\tPPL\tXX\t\t\t\t\t\t1000\t\t0\tEtc/UTC\t2024-01-01"
+        )
+    }
+
+    fn cities(rows: &[(u64, &str, f64, f64)]) -> Cities {
+        let mut cities = Cities::default();
+        for (id, name, latitude, longitude) in rows {
+            cities.insert(parse_line(&line(*id, name, *latitude, *longitude)).unwrap());
+        }
+        cities
+    }
+
+    fn names(found: &[(&City, f64)]) -> Vec<String> {
+        found.iter().map(|(city, _)| city.name.clone()).collect()
+    }
+
+    #[test]
+    fn nearest_wraps_around_the_antimeridian() {
+        let cities = cities(&[(1, "East", -18.0, -179.8), (2, "West", -18.0, 179.0)]);
+        let found = cities.nearest(-18.0, 179.9, 2);
+        assert_eq!(names(&found), ["East", "West"]);
+        assert!(found[0].1 < 35.0, "{}", found[0].1);
+
+        assert_eq!(cell_of(-18.0, 180.0), (-18, -180));
+        assert_eq!(cell_of(-18.0, -180.0), (-18, -180));
+    }
+
+    #[test]
+    fn widest_ring_visits_every_column_once() {
+        let mut columns: Vec<i32> = ring_offsets(180)
+            .into_iter()
+            .filter(|(d_row, _)| *d_row == -180)
+            .map(|(_, d_column)| (d_column + 180).rem_euclid(360))
+            .collect();
+        columns.sort();
+        columns.dedup();
+        assert_eq!(columns.len(), 360);
+        assert_eq!(ring_offsets(180).len(), 2 * 360 + 359);
+    }
+
+    #[test]
+    fn nearest_searches_across_the_pole() {
+        let cities = cities(&[(1, "South", 80.0, 0.0), (2, "Across", 89.9, 170.0)]);
+        let found = cities.nearest(89.5, 0.0, 1);
+        assert_eq!(names(&found), ["Across"]);
+        assert!(found[0].1 < 70.0, "{}", found[0].1);
+
+        let found = cities.nearest(89.5, 0.0, 2);
+        assert_eq!(names(&found), ["Across", "South"]);
+    }
+
+    #[test]
+    fn nearest_returns_every_city_when_limit_is_larger() {
+        let cities = cities(&[
+            (1, "Far", 10.0, 10.0),
+            (2, "Near", 0.5, 0.5),
+            (3, "Middle", 3.0, -3.0),
+        ]);
+        let found = cities.nearest(0.0, 0.0, 10);
+        assert_eq!(names(&found), ["Near", "Middle", "Far"]);
+        assert!(cities.nearest(0.0, 0.0, 0).is_empty());
+    }
+
+    #[test]
+    fn parse_line_skips_malformed_lines() {
+        let city = parse_line(&line(2988507, "Paris", 48.85341, 2.3488)).unwrap();
+        assert_eq!((city.id, city.name.as_str()), (2988507, "Paris"));
+        assert_eq!((city.latitude, city.longitude), (48.85341, 2.3488));
+        assert_eq!((city.population, city.timezone.as_str()), (1000, "Etc/UTC"));
+
+        assert!(parse_line("2988507\tParis\tParis").is_none());
+        assert!(
+            parse_line(&line(1, "Paris", 0.0, 0.0).replacen("\t0\t", "\tnorth\t", 1)).is_none()
+        );
+        assert!(parse_line(&line(1, "Paris", 0.0, 0.0).replacen("1\t", "x\t", 1)).is_none());
+    }
+}
@@ -0,0 +1,171 @@
+//! HTTP transport: MCP over Server-Sent Events (the HTTP transport of protocol
+//! version 2024-11-05) plus a health-check route, so that one server instance
+//! can be shared by several clients.
+//!
+//! Sessions are handled by rmcp's `SseServer`: a client opens `GET /sse`,
+//! receives an `endpoint` event with its session URL, and posts its JSON-RPC
+//! messages to `POST /message?sessionId=...`. `SseServer` owns its router, so
+//! the health check is served on a listener of its own.
+
+use std::net::SocketAddr;
+
+use axum::routing::get;
+use axum::{Json, Router};
+use rmcp::transport::sse_server::SseServer;
+use rmcp::{RoleServer, Service};
+
+/// Path of the health check.
+const HEALTH_PATH: &str = "/health";
+
+/// Serves MCP over HTTP on `bind` and the health check on `health_bind` until
+/// Ctrl-C, starting one service from `service_provider` per client session.
+pub async fn serve<S, F>(
+    bind: SocketAddr,
+    health_bind: SocketAddr,
+    service_provider: F,
+) -> Result<(), anyhow::Error>
+where
+    S: Service<RoleServer>,
+    F: Fn() -> S + Send + 'static,
+{
+    let health_listener = tokio::net::TcpListener::bind(health_bind).await?;
+    let sse = SseServer::serve(bind).await?;
+    tracing::info!(
+        "Listening on http://{} (SSE: {}, messages: {}), health check on http://{}{}",
+        sse.config.bind,
+        sse.config.sse_path,
+        sse.config.post_path,
+        health_listener.local_addr()?,
+        HEALTH_PATH
+    );
+    let ct = sse.with_service(service_provider);
+
+    let health = tokio::spawn({
+        let ct = ct.clone();
+        async move {
+            axum::serve(health_listener, health_router())
+                .with_graceful_shutdown(async move { ct.cancelled().await })
+                .await
+        }
+    });
+
+    tokio::signal::ctrl_c().await?;
+    tracing::info!("Shutting down HTTP transport");
+    ct.cancel();
+    health.await??;
+    Ok(())
+}
+
+fn health_router() -> Router {
+    Router::new().route(
+        HEALTH_PATH,
+        get(|| async {
+            Json(serde_json::json!({
+                "status": "ok",
+                "name": env!("CARGO_PKG_NAME"),
+                "version": env!("CARGO_PKG_VERSION"),
+            }))
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Config, OpenMeteoServer};
+    use clap::Parser;
+
+    /// Reads the SSE stream until a whole event arrives and returns its
+    /// `event` and `data` fields.
+    async fn next_event(response: &mut reqwest::Response, buffer: &mut String) -> (String, String) {
+        loop {
+            if let Some(end) = buffer.find("\n\n") {
+                let block: String = buffer.drain(..end + 2).collect();
+                let field = |name: &str| {
+                    block
+                        .lines()
+                        .find_map(|line| line.strip_prefix(name))
+                        .map(|value| value.trim_start().to_string())
+                };
+                if let (Some(event), Some(data)) = (field("event:"), field("data:")) {
+                    return (event, data);
+                }
+                continue;
+            }
+            let chunk = response.chunk().await.unwrap().expect("stream ended");
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+
+    /// A local address with a free port; `SseServer` reports the address it
+    /// was asked to bind rather than the one it got, so port 0 won't do.
+    fn free_address() -> SocketAddr {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn sse_session_round_trip() {
+        let server = OpenMeteoServer::new(Config::parse_from(["mcp-server-openmeteo"])).unwrap();
+        let bind = free_address();
+        let ct = SseServer::serve(bind)
+            .await
+            .unwrap()
+            .with_service(move || server.clone());
+        let base = format!("http://{}", bind);
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let mut stream = client.get(format!("{base}/sse")).send().await.unwrap();
+        assert_eq!(stream.status(), reqwest::StatusCode::OK);
+        let mut buffer = String::new();
+        let (event, endpoint) = next_event(&mut stream, &mut buffer).await;
+        assert_eq!(event, "endpoint");
+        assert!(endpoint.starts_with("/message?sessionId="), "{}", endpoint);
+
+        let initialize = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": {"name": "test", "version": "0.0.0"},
+            },
+        });
+        let posted = client
+            .post(format!("{base}{endpoint}"))
+            .json(&initialize)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(posted.status(), reqwest::StatusCode::ACCEPTED);
+
+        let (event, data) = next_event(&mut stream, &mut buffer).await;
+        assert_eq!(event, "message");
+        let response: serde_json::Value = serde_json::from_str(&data).unwrap();
+        assert_eq!(response["id"], 1);
+        assert!(response["result"]["serverInfo"].is_object(), "{}", data);
+        ct.cancel();
+    }
+
+    #[tokio::test]
+    async fn health_check_reports_name_and_version() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, health_router()).await });
+
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        let health: serde_json::Value = client
+            .get(format!("{base}{HEALTH_PATH}"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(health["status"], "ok");
+        assert_eq!(health["name"], env!("CARGO_PKG_NAME"));
+    }
+}
@@ -1,4 +1,35 @@
-use chrono::NaiveDate;
+mod agriculture;
+mod air_quality;
+mod archive;
+mod cache;
+mod climate;
+mod config;
+mod elevation;
+mod ensemble;
+mod flood;
+mod forecast;
+mod geocoding;
+mod geonames;
+mod http;
+mod marine;
+mod seasonal;
+mod solar;
+mod stats;
+mod units;
+mod upper_air;
+mod variables;
+mod verification;
+
+use agriculture::{ARCHIVE_SOIL, FORECAST_SOIL};
+use cache::ResponseCache;
+use chrono::{NaiveDate, NaiveDateTime};
+use clap::Parser;
+use climate::{CLIMATE_MODELS, DEFAULT_CLIMATE_MODELS};
+use config::{Api, Config, Transport};
+use ensemble::{EnsembleVariable, ENSEMBLE_MODELS};
+use flood::DischargeWindow;
+use geocoding::{SearchOptions, FEATURE_CODES};
+use geonames::Cities;
 use rmcp::{
     model::{
         CallToolResult, Content, Implementation, ProtocolVersion, ServerCapabilities, ServerInfo,
@@ -7,72 +38,703 @@ use rmcp::{
     transport::stdio,
     Error as McpError, ServerHandler, ServiceExt,
 };
+use seasonal::MAX_SEASONAL_FORECAST_DAYS;
 use serde_json::Value;
+use std::collections::BTreeMap;
 use std::env;
 use std::sync::Arc;
+use units::{UnitParams, Units};
+use upper_air::{DEFAULT_UPPER_AIR_LEVELS, INDEX_LEVELS, PRESSURE_LEVELS};
+use variables::{Block, Source, Variable};
+use verification::VERIFICATION_VARIABLES;
+
+/// Timezone parameter accepted by every weather tool.
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+struct TimezoneParams {
+    #[schemars(
+        description = "Timezone for times and daily boundaries: 'auto' (default, the location's local time) or an IANA name such as 'Europe/Paris' or 'GMT'"
+    )]
+    timezone: Option<String>,
+}
+
+impl TimezoneParams {
+    /// The requested timezone, 'auto' when none is given.
+    fn name(&self) -> &str {
+        self.timezone.as_deref().unwrap_or("auto")
+    }
+}
+
+/// Point of a weather tool, given as coordinates or as a place name.
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+struct LocationParams {
+    #[schemars(
+        description = "Latitude coordinate (-90 to 90). Required unless 'location' is given."
+    )]
+    latitude: Option<f64>,
+    #[schemars(
+        description = "Longitude coordinate (-180 to 180). Required unless 'location' is given."
+    )]
+    longitude: Option<f64>,
+    #[schemars(
+        description = "Place name to use instead of latitude/longitude, e.g. 'Paris, France' or 'Springfield, Illinois'. Resolved to the best geocoding match."
+    )]
+    location: Option<String>,
+}
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 struct GetCurrentWeatherParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "Optional list of up to 100 points, each with 'latitude' and 'longitude', to query in one request instead of a single 'latitude'/'longitude' or 'location'"
+    )]
+    locations: Option<Vec<Coordinates>>,
+    #[schemars(
+        description = "Optional weather models, e.g. ['ecmwf_ifs025', 'gfs_seamless', 'icon_seamless']. Several models are shown side-by-side. Defaults to Open-Meteo's best match."
+    )]
+    models: Option<Vec<String>>,
+    #[schemars(
+        description = "Optional list of extra current-condition variables to include (e.g. ['dew_point_2m', 'uv_index']), shown in a table below the report"
+    )]
+    variables: Option<Vec<String>>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetWeatherForecastParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "Optional list of up to 100 points, each with 'latitude' and 'longitude', to query in one request instead of a single 'latitude'/'longitude' or 'location'"
+    )]
+    locations: Option<Vec<Coordinates>>,
+    #[schemars(
+        description = "Number of forecast days (1-16, default: 7 unless the server is configured otherwise)"
+    )]
+    days: Option<u32>,
+    #[schemars(
+        description = "Optional weather models, e.g. ['ecmwf_ifs025', 'gfs_seamless', 'icon_seamless']. Several models are shown side-by-side per day. Defaults to Open-Meteo's best match."
+    )]
+    models: Option<Vec<String>>,
+    #[schemars(
+        description = "Optional list of extra daily or hourly variables to include (e.g. ['uv_index_clear_sky_max', 'dew_point_2m']), shown in tables below the forecast"
+    )]
+    variables: Option<Vec<String>>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetHourlyForecastParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(description = "Number of forecast hours (1-384, default: 24)")]
+    hours: Option<u32>,
+    #[schemars(
+        description = "Optional start time in ISO 8601 format (YYYY-MM-DDTHH:MM) in the selected timezone. Defaults to the current hour."
+    )]
+    start_time: Option<String>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetHistoricalWeatherParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "Optional list of up to 100 points, each with 'latitude' and 'longitude', to query in one request instead of a single 'latitude'/'longitude' or 'location'"
+    )]
+    locations: Option<Vec<Coordinates>>,
+    #[schemars(description = "Start date (YYYY-MM-DD)")]
+    start_date: String,
+    #[schemars(description = "End date (YYYY-MM-DD)")]
+    end_date: String,
+    #[schemars(
+        description = "Optional reanalysis models, e.g. ['era5', 'era5_land', 'cerra']. Several models are shown side-by-side per day. Defaults to Open-Meteo's best match."
+    )]
+    models: Option<Vec<String>>,
+    #[schemars(
+        description = "Optional list of extra daily or hourly variables to include (e.g. ['et0_fao_evapotranspiration', 'soil_moisture_0_to_7cm']), shown in tables below the report"
+    )]
+    variables: Option<Vec<String>>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetAirQualityParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetMarineForecastParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(description = "Forecast resolution: 'daily' (default) or 'hourly'")]
+    resolution: Option<String>,
+    #[schemars(description = "Number of forecast days (1-16, default: 7 for daily, 1 for hourly)")]
+    days: Option<u32>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetRiverDischargeParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(description = "Number of forecast days (1-210, default: 92)")]
+    forecast_days: Option<u32>,
+    #[schemars(description = "Number of past days to include (0-92, default: 7)")]
+    past_days: Option<u32>,
+    #[schemars(
+        description = "Optional start date (YYYY-MM-DD). Overrides forecast_days/past_days together with end_date."
+    )]
+    start_date: Option<String>,
+    #[schemars(
+        description = "Optional end date (YYYY-MM-DD). Overrides forecast_days/past_days together with start_date."
+    )]
+    end_date: Option<String>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetEnsembleForecastParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "Ensemble model: 'icon_eps' (default), 'gfs_ensemble', 'ecmwf_ifs_ensemble', or 'gem_ensemble'"
+    )]
+    model: Option<String>,
+    #[schemars(
+        description = "Daily variable: 'precipitation' (default, daily sum), 'temperature_max', 'temperature_min', or 'wind_speed_max'"
+    )]
+    variable: Option<String>,
+    #[schemars(
+        description = "Optional threshold for exceedance probabilities, in the variable's unit (e.g. 5 for P(precip > 5mm))"
+    )]
+    threshold: Option<f64>,
+    #[schemars(
+        description = "Number of forecast days (1-35, default: 7). Available range depends on the model."
+    )]
+    days: Option<u32>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetClimateProjectionParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "CMIP6 models to compare (default: EC_Earth3P_HR, MPI_ESM1_2_XR, MRI_AGCM3_2_S). Available: CMCC_CM2_VHR4, FGOALS_f3_H, HiRAM_SIT_HR, MRI_AGCM3_2_S, EC_Earth3P_HR, MPI_ESM1_2_XR, NICAM16_8S"
+    )]
+    models: Option<Vec<String>>,
+    #[schemars(description = "First year of the projection (1950-2050)")]
+    start_year: i32,
+    #[schemars(description = "Last year of the projection (1950-2050)")]
+    end_year: i32,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetSeasonalForecastParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(description = "Number of months ahead (1-9, default: 6)")]
+    months: Option<u32>,
+    #[schemars(description = "Summary period: 'monthly' (default) or 'weekly'")]
+    period: Option<String>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, schemars::JsonSchema)]
+struct Coordinates {
     #[schemars(description = "Latitude coordinate (-90 to 90)")]
     latitude: f64,
     #[schemars(description = "Longitude coordinate (-180 to 180)")]
     longitude: f64,
 }
 
+impl Coordinates {
+    /// `latitude=..&longitude=..` with comma-separated lists for several points.
+    fn to_query(locations: &[Coordinates]) -> String {
+        let latitudes: Vec<String> = locations.iter().map(|c| c.latitude.to_string()).collect();
+        let longitudes: Vec<String> = locations.iter().map(|c| c.longitude.to_string()).collect();
+        format!(
+            "latitude={}&longitude={}",
+            latitudes.join(","),
+            longitudes.join(",")
+        )
+    }
+}
+
+/// A point a report is produced for, with the geocoded name when it was
+/// given as a place name.
+#[derive(Debug, Clone)]
+struct Place {
+    latitude: f64,
+    longitude: f64,
+    label: Option<String>,
+}
+
+impl Place {
+    fn coordinates(&self) -> Coordinates {
+        Coordinates {
+            latitude: self.latitude,
+            longitude: self.longitude,
+        }
+    }
+}
+
+/// Renders the "Location:" value of report headers, leading with the place
+/// name when there is one.
+impl std::fmt::Display for Place {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(label) = &self.label {
+            write!(f, "{} — ", label)?;
+        }
+        write!(f, "{:.2}°, {:.2}°", self.latitude, self.longitude)
+    }
+}
+
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
-struct GetWeatherForecastParams {
+struct ReverseGeocodeParams {
     #[schemars(description = "Latitude coordinate (-90 to 90)")]
     latitude: f64,
     #[schemars(description = "Longitude coordinate (-180 to 180)")]
     longitude: f64,
+    #[schemars(description = "Number of nearest places to return (1-20, default: 5)")]
+    limit: Option<u32>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetElevationParams {
+    #[schemars(description = "Points to look up (1-100)")]
+    locations: Vec<Coordinates>,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetSolarRadiationParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(description = "Panel tilt in degrees from horizontal (0-90, default: 30)")]
+    tilt: Option<f64>,
+    #[schemars(
+        description = "Panel azimuth in degrees: 0 = south, -90 = east, 90 = west, 180 = north (default: 0)"
+    )]
+    azimuth: Option<f64>,
+    #[schemars(description = "Optional PV system size in kWp, used to estimate energy yield")]
+    system_size_kwp: Option<f64>,
+    #[schemars(description = "Overall system efficiency / performance ratio (0-1, default: 0.8)")]
+    efficiency: Option<f64>,
     #[schemars(description = "Number of forecast days (1-16, default: 7)")]
     days: Option<u32>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
-struct GetHistoricalWeatherParams {
-    #[schemars(description = "Latitude coordinate (-90 to 90)")]
-    latitude: f64,
-    #[schemars(description = "Longitude coordinate (-180 to 180)")]
-    longitude: f64,
+struct GetHistoricalForecastParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(description = "Start date (YYYY-MM-DD)")]
+    start_date: String,
+    #[schemars(description = "End date (YYYY-MM-DD)")]
+    end_date: String,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetPreviousModelRunsParams {
+    #[serde(flatten)]
+    place: LocationParams,
     #[schemars(description = "Start date (YYYY-MM-DD)")]
     start_date: String,
     #[schemars(description = "End date (YYYY-MM-DD)")]
     end_date: String,
+    #[schemars(
+        description = "Forecast lead time in days, i.e. the run issued this many days before each date (1-7, default: 3)"
+    )]
+    lead_days: Option<u32>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetUpperAirParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "Pressure levels in hPa (default: [1000, 925, 850, 700, 500, 300]). Available: 1000, 975, 950, 925, 900, 850, 800, 700, 600, 500, 400, 300, 250, 200, 150, 100, 70, 50, 30"
+    )]
+    levels: Option<Vec<u32>>,
+    #[schemars(description = "Number of forecast hours (1-384, default: 12)")]
+    hours: Option<u32>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetAgricultureWeatherParams {
+    #[serde(flatten)]
+    place: LocationParams,
+    #[schemars(
+        description = "Number of past days from the historical archive, ending yesterday (0-92, default: 7)"
+    )]
+    past_days: Option<u32>,
+    #[schemars(description = "Number of forecast days (0-16, default: 7)")]
+    forecast_days: Option<u32>,
+    #[serde(flatten)]
+    timezone: TimezoneParams,
+    #[serde(flatten)]
+    units: UnitParams,
+}
+
+#[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
+struct GetLocationParams {
+    #[schemars(
+        description = "Geocoding ID of the place, as shown by search_locations (e.g. 2988507 for Paris)"
+    )]
+    id: u64,
+    #[schemars(
+        description = "Two-letter ISO 639-1 language code for place names, e.g. 'de' or 'fr' (default: 'en')"
+    )]
+    language: Option<String>,
 }
 
 #[derive(Debug, serde::Deserialize, schemars::JsonSchema)]
 struct SearchLocationsParams {
-    #[schemars(description = "Location search query in format 'city, country' (country is optional). Examples: 'Paris, France', 'Tokyo', 'New York, USA'")]
+    #[schemars(
+        description = "Location search query in format 'city, country' (country is optional). Examples: 'Paris, France', 'Tokyo', 'New York, USA'"
+    )]
     query: String,
     #[schemars(description = "Maximum number of results (default: 10)")]
     limit: Option<u32>,
+    #[schemars(
+        description = "Two-letter ISO 639-1 language code for place names, e.g. 'de' or 'fr' (default: 'en')"
+    )]
+    language: Option<String>,
+    #[schemars(
+        description = "Optional two-letter ISO 3166-1 country code to restrict results to, e.g. 'FR' or 'US'"
+    )]
+    country_code: Option<String>,
+    #[schemars(
+        description = "Optional minimum population; places without a known population are excluded"
+    )]
+    min_population: Option<u64>,
+}
+
+/// Forecast models selectable on the forecast API as (model name, display label).
+const FORECAST_MODELS: [(&str, &str); 22] = [
+    ("best_match", "Best match"),
+    ("ecmwf_ifs025", "ECMWF IFS"),
+    ("ecmwf_aifs025_single", "ECMWF AIFS"),
+    ("gfs_seamless", "NCEP GFS"),
+    ("icon_seamless", "DWD ICON"),
+    ("icon_global", "DWD ICON Global"),
+    ("icon_eu", "DWD ICON-EU"),
+    ("icon_d2", "DWD ICON-D2"),
+    ("meteofrance_seamless", "Météo-France"),
+    ("meteofrance_arpege_world", "Météo-France ARPEGE World"),
+    ("meteofrance_arpege_europe", "Météo-France ARPEGE Europe"),
+    ("meteofrance_arome_france", "Météo-France AROME France"),
+    ("jma_seamless", "JMA"),
+    ("gem_seamless", "GEM (Environment Canada)"),
+    ("ukmo_seamless", "UK Met Office"),
+    ("metno_nordic", "MET Norway Nordic"),
+    ("knmi_seamless", "KNMI"),
+    ("dmi_seamless", "DMI"),
+    ("cma_grapes_global", "CMA GRAPES"),
+    ("bom_access_global", "BOM ACCESS"),
+    ("kma_seamless", "KMA"),
+    ("italia_meteo_arpae_icon_2i", "ItaliaMeteo ICON-2I"),
+];
+
+/// Reanalysis models selectable on the archive API as (model name, display label).
+const ARCHIVE_MODELS: [(&str, &str); 8] = [
+    ("best_match", "Best match"),
+    ("era5_seamless", "ERA5 seamless"),
+    ("era5", "ERA5"),
+    ("era5_land", "ERA5-Land"),
+    ("era5_ensemble", "ERA5 Ensemble"),
+    ("ecmwf_ifs", "ECMWF IFS"),
+    ("ecmwf_ifs_analysis_long_window", "ECMWF IFS Analysis"),
+    ("cerra", "CERRA"),
+];
+
+/// Optional query parameters shared by the weather fetch helpers.
+#[derive(Debug, Default, Clone)]
+struct QueryOptions {
+    models: Vec<String>,
+    units: Units,
+    timezone: String,
+    /// Extra variables on top of the fetcher's defaults.
+    variables: Vec<Variable>,
+}
+
+impl QueryOptions {
+    fn to_query(&self) -> String {
+        let mut query = String::new();
+        if !self.models.is_empty() {
+            query.push_str(&format!("&models={}", self.models.join(",")));
+        }
+        query.push_str(&self.units.to_query());
+        query.push_str(&self.timezone_query());
+        query
+    }
+
+    /// The marine API takes a length unit instead of wind and precipitation units.
+    fn to_marine_query(&self) -> String {
+        let mut query = self.units.to_marine_query();
+        query.push_str(&self.timezone_query());
+        query
+    }
+
+    fn timezone_query(&self) -> String {
+        if self.timezone.is_empty() {
+            String::new()
+        } else {
+            format!("&timezone={}", urlencoding::encode(&self.timezone))
+        }
+    }
 }
 
+/// Hourly rows of a requested-variables table; longer series are cut off.
+const MAX_HOURLY_TABLE_ROWS: usize = 48;
+
+/// Climate normals kept in memory. Normals never change, so entries only leave
+/// the cache to make room.
+const NORMALS_CACHE_SIZE: usize = 256;
+
+/// Geocoding results considered when resolving a 'location' name.
+const GEOCODE_CANDIDATES: u32 = 10;
+
+/// Geocoding result fields a qualifier such as 'France' or 'Illinois' may
+/// match, or start with as in 'Fr'.
+const PLACE_QUALIFIER_FIELDS: [&str; 6] = [
+    "country",
+    "country_code",
+    "admin1",
+    "admin2",
+    "admin3",
+    "admin4",
+];
+
+/// Postal abbreviations of US states and Canadian provinces, which geocoding
+/// results only carry as the full 'admin1' name: (country code, abbreviation,
+/// name).
+const REGION_ABBREVIATIONS: [(&str, &str, &str); 63] = [
+    ("US", "AL", "Alabama"),
+    ("US", "AK", "Alaska"),
+    ("US", "AZ", "Arizona"),
+    ("US", "AR", "Arkansas"),
+    ("US", "CA", "California"),
+    ("US", "CO", "Colorado"),
+    ("US", "CT", "Connecticut"),
+    ("US", "DE", "Delaware"),
+    ("US", "FL", "Florida"),
+    ("US", "GA", "Georgia"),
+    ("US", "HI", "Hawaii"),
+    ("US", "ID", "Idaho"),
+    ("US", "IL", "Illinois"),
+    ("US", "IN", "Indiana"),
+    ("US", "IA", "Iowa"),
+    ("US", "KS", "Kansas"),
+    ("US", "KY", "Kentucky"),
+    ("US", "LA", "Louisiana"),
+    ("US", "ME", "Maine"),
+    ("US", "MD", "Maryland"),
+    ("US", "MA", "Massachusetts"),
+    ("US", "MI", "Michigan"),
+    ("US", "MN", "Minnesota"),
+    ("US", "MS", "Mississippi"),
+    ("US", "MO", "Missouri"),
+    ("US", "MT", "Montana"),
+    ("US", "NE", "Nebraska"),
+    ("US", "NV", "Nevada"),
+    ("US", "NH", "New Hampshire"),
+    ("US", "NJ", "New Jersey"),
+    ("US", "NM", "New Mexico"),
+    ("US", "NY", "New York"),
+    ("US", "NC", "North Carolina"),
+    ("US", "ND", "North Dakota"),
+    ("US", "OH", "Ohio"),
+    ("US", "OK", "Oklahoma"),
+    ("US", "OR", "Oregon"),
+    ("US", "PA", "Pennsylvania"),
+    ("US", "RI", "Rhode Island"),
+    ("US", "SC", "South Carolina"),
+    ("US", "SD", "South Dakota"),
+    ("US", "TN", "Tennessee"),
+    ("US", "TX", "Texas"),
+    ("US", "UT", "Utah"),
+    ("US", "VT", "Vermont"),
+    ("US", "VA", "Virginia"),
+    ("US", "WA", "Washington"),
+    ("US", "WV", "West Virginia"),
+    ("US", "WI", "Wisconsin"),
+    ("US", "WY", "Wyoming"),
+    ("CA", "AB", "Alberta"),
+    ("CA", "BC", "British Columbia"),
+    ("CA", "MB", "Manitoba"),
+    ("CA", "NB", "New Brunswick"),
+    ("CA", "NL", "Newfoundland and Labrador"),
+    ("CA", "NS", "Nova Scotia"),
+    ("CA", "NT", "Northwest Territories"),
+    ("CA", "NU", "Nunavut"),
+    ("CA", "ON", "Ontario"),
+    ("CA", "PE", "Prince Edward Island"),
+    ("CA", "QC", "Quebec"),
+    ("CA", "SK", "Saskatchewan"),
+    ("CA", "YT", "Yukon"),
+];
+
+/// Daily values of one variable keyed by date (YYYY-MM-DD).
+type DailySeries = BTreeMap<String, f64>;
+
+/// Places further away than this are not used to label coordinates, in km.
+const NEAREST_PLACE_MAX_KM: f64 = 50.0;
+
 #[derive(Clone)]
 struct OpenMeteoServer {
     client: Arc<reqwest::Client>,
+    config: Arc<Config>,
+    cache: Arc<ResponseCache>,
+    /// Daily climate normals per rounded coordinate, see `climate_normals`.
+    normals: Arc<ResponseCache>,
+    /// Reverse geocoding index; absent when no GeoNames file is configured.
+    cities: Option<Arc<Cities>>,
+}
+
+/// Splits a 'location' into the place name and the lowercase qualifiers
+/// after it, e.g. 'Paris, France' into 'Paris' and ['france'].
+fn split_place_query(query: &str) -> (&str, Vec<String>) {
+    match query.split_once(',') {
+        Some((name, rest)) => (
+            name.trim(),
+            rest.split(',')
+                .map(|part| part.trim().to_lowercase())
+                .filter(|part| !part.is_empty())
+                .collect(),
+        ),
+        None => (query.trim(), Vec::new()),
+    }
+}
+
+/// Whether a lowercase qualifier names a geocoding result's country or an
+/// administrative area, in full, as a prefix, or as a US state or Canadian
+/// province abbreviation.
+fn qualifier_matches(result: &Value, qualifier: &str) -> bool {
+    let field = |key: &str| {
+        result
+            .get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_lowercase())
+    };
+    let named = PLACE_QUALIFIER_FIELDS
+        .iter()
+        .any(|key| field(key).is_some_and(|value| value.starts_with(qualifier)));
+    named
+        || REGION_ABBREVIATIONS
+            .iter()
+            .any(|(country, abbreviation, name)| {
+                abbreviation.eq_ignore_ascii_case(qualifier)
+                    && field("country_code").as_deref() == Some(country.to_lowercase().as_str())
+                    && field("admin1").as_deref() == Some(name.to_lowercase().as_str())
+            })
+}
+
+/// Replaces the URL of a request error, which carries the API key, with the
+/// `url` the request was built from.
+fn redact_api_key(error: reqwest::Error, url: &str) -> reqwest::Error {
+    match reqwest::Url::parse(url) {
+        Ok(url) => error.with_url(url),
+        Err(_) => error.without_url(),
+    }
 }
 
 impl OpenMeteoServer {
-    fn new() -> Result<Self, anyhow::Error> {
+    fn new(config: Config) -> Result<Self, anyhow::Error> {
         let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
+            .timeout(std::time::Duration::from_secs(config.timeout))
             .build()?;
 
+        let cities = match &config.geonames_file {
+            Some(path) => {
+                let cities = Cities::load(path)?;
+                if cities.is_empty() {
+                    tracing::warn!("GeoNames file {} contains no places", path.display());
+                }
+                tracing::info!(
+                    "Loaded {} GeoNames places from {}",
+                    cities.len(),
+                    path.display()
+                );
+                Some(Arc::new(cities))
+            }
+            None => None,
+        };
+
+        if config.api_key.is_some() {
+            tracing::info!("Using the Open-Meteo commercial API");
+        }
+
+        let cache = ResponseCache::new(
+            std::time::Duration::from_secs(config.cache_ttl),
+            config.cache_size,
+        );
+
         Ok(Self {
             client: Arc::new(client),
+            config: Arc::new(config),
+            cache: Arc::new(cache),
+            normals: Arc::new(ResponseCache::new(
+                std::time::Duration::MAX,
+                NORMALS_CACHE_SIZE,
+            )),
+            cities,
         })
     }
 
     fn validate_coordinates(&self, latitude: f64, longitude: f64) -> Result<(), String> {
-        if latitude < -90.0 || latitude > 90.0 {
+        if !(-90.0..=90.0).contains(&latitude) {
             return Err(format!(
                 "Invalid latitude: {}. Must be between -90 and 90.",
                 latitude
             ));
         }
-        if longitude < -180.0 || longitude > 180.0 {
+        if !(-180.0..=180.0).contains(&longitude) {
             return Err(format!(
                 "Invalid longitude: {}. Must be between -180 and 180.",
                 longitude
@@ -81,464 +743,624 @@ impl OpenMeteoServer {
         Ok(())
     }
 
-    fn validate_date(&self, date_str: &str) -> Result<NaiveDate, String> {
-        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
-            .map_err(|_| format!("Invalid date format: '{}'. Expected YYYY-MM-DD.", date_str))
+    /// Validates the unit and timezone parameters of a weather tool into the
+    /// options of its fetch helper. Units resolve against the configured
+    /// preset; tools without unit parameters pass `None` and get the metric
+    /// API defaults. Invalid parameters come back as the error result the
+    /// tool returns.
+    fn resolve_options(
+        &self,
+        units: Option<&UnitParams>,
+        timezone: &TimezoneParams,
+    ) -> Result<QueryOptions, CallToolResult> {
+        let invalid = |what: &str, err: String| {
+            tracing::error!("Invalid {}: {}", what, err);
+            CallToolResult::error(vec![Content::text(err)])
+        };
+        let units = match units {
+            Some(params) => params
+                .resolve(self.config.units)
+                .map_err(|err| invalid("units", err))?,
+            None => Units::default(),
+        };
+        let timezone = timezone.name();
+        self.validate_timezone(timezone)
+            .map_err(|err| invalid("timezone", err))?;
+        Ok(QueryOptions {
+            units,
+            timezone: timezone.to_string(),
+            ..Default::default()
+        })
+    }
+
+    /// Point of a single-location tool call. An invalid or unknown location
+    /// comes back as the error result the tool returns.
+    async fn resolve_location(&self, place: &LocationParams) -> Result<Place, CallToolResult> {
+        let mut places = self.resolve_locations(place, None).await?;
+        Ok(places.remove(0))
     }
 
-    async fn fetch_current_weather(
+    /// Points of a tool call given as 'latitude'/'longitude', a 'location'
+    /// name, or a 'locations' list. An invalid or unknown location comes back
+    /// as the error result the tool returns.
+    async fn resolve_locations(
         &self,
-        latitude: f64,
-        longitude: f64,
-    ) -> Result<Value, anyhow::Error> {
-        let url = format!(
-            "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current=temperature_2m,relative_humidity_2m,apparent_temperature,is_day,precipitation,rain,showers,snowfall,weather_code,cloud_cover,pressure_msl,surface_pressure,wind_speed_10m,wind_direction_10m,wind_gusts_10m",
-            latitude, longitude, 
-        );
+        place: &LocationParams,
+        locations: Option<&[Coordinates]>,
+    ) -> Result<Vec<Place>, CallToolResult> {
+        self.places(place, locations).await.map_err(|err| {
+            tracing::error!("Invalid location: {}", err);
+            CallToolResult::error(vec![Content::text(err)])
+        })
+    }
+
+    /// See `resolve_locations`; each point of a 'locations' list is validated
+    /// with its number in the error.
+    async fn places(
+        &self,
+        place: &LocationParams,
+        locations: Option<&[Coordinates]>,
+    ) -> Result<Vec<Place>, String> {
+        let LocationParams {
+            latitude,
+            longitude,
+            location,
+        } = place;
+        let Some(locations) = locations else {
+            return match (*latitude, *longitude, location) {
+                (Some(latitude), Some(longitude), None) => {
+                    self.validate_coordinates(latitude, longitude)?;
+                    Ok(vec![Place {
+                        latitude,
+                        longitude,
+                        label: None,
+                    }])
+                }
+                (None, None, Some(location)) => Ok(vec![self.geocode(location).await?]),
+                _ => Err(
+                    "Either both 'latitude' and 'longitude' or a 'location' name must be given."
+                        .to_string(),
+                ),
+            };
+        };
+        if latitude.is_some() || longitude.is_some() || location.is_some() {
+            return Err(
+                "Give either a 'locations' list or a single 'latitude'/'longitude' or 'location', not both."
+                    .to_string(),
+            );
+        }
+        if locations.is_empty() || locations.len() > 100 {
+            return Err(format!(
+                "Invalid number of locations: {}. Must be between 1 and 100.",
+                locations.len()
+            ));
+        }
+        for (i, location) in locations.iter().enumerate() {
+            self.validate_coordinates(location.latitude, location.longitude)
+                .map_err(|err| format!("Location {}: {}", i + 1, err))?;
+        }
+        Ok(locations
+            .iter()
+            .map(|location| Place {
+                latitude: location.latitude,
+                longitude: location.longitude,
+                label: None,
+            })
+            .collect())
+    }
 
-        let response = self.client.get(&url).send().await?;
+    /// Resolves a place name to the top geocoding match, see `best_match`.
+    async fn geocode(&self, query: &str) -> Result<Place, String> {
+        let (name, _) = split_place_query(query);
+        if name.is_empty() {
+            return Err("The place name in 'location' is empty.".to_string());
+        }
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "OpenMeteo API error: {}",
-                response.status()
+        let data = self
+            .search_locations_helper(name, GEOCODE_CANDIDATES, &SearchOptions::default())
+            .await
+            .map_err(|e| format!("Error resolving location '{}': {}", query, e))?;
+        self.best_match(query, &data)
+    }
+
+    /// Top result of a geocoding search for `query`. A qualifier after the
+    /// first comma ('Paris, France', 'Springfield, IL') must match the
+    /// country, country code, or an administrative area, see
+    /// `qualifier_matches`. Several matches of the same name with comparable
+    /// population are reported as ambiguous.
+    fn best_match(&self, query: &str, data: &Value) -> Result<Place, String> {
+        let (name, qualifiers) = split_place_query(query);
+        let empty_vec = vec![];
+        let results = data
+            .get("results")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let field = |result: &Value, key: &str| {
+            result
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_lowercase())
+        };
+        let candidates: Vec<&Value> = results
+            .iter()
+            .filter(|result| {
+                qualifiers
+                    .iter()
+                    .all(|qualifier| qualifier_matches(result, qualifier))
+            })
+            .collect();
+
+        let Some(top) = candidates.first() else {
+            if results.is_empty() {
+                return Err(format!("No location found for '{}'.", query));
+            }
+            let suggestions: Vec<String> = results.iter().map(|r| self.place_label(r)).collect();
+            return Err(format!(
+                "No location found for '{}'. Matches for '{}': {}.",
+                query,
+                name,
+                suggestions.join("; ")
+            ));
+        };
+
+        let population = |result: &Value| {
+            result
+                .get("population")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0)
+        };
+        let strong: Vec<&Value> = candidates
+            .iter()
+            .copied()
+            .filter(|result| {
+                field(result, "name").as_deref() == Some(name.to_lowercase().as_str())
+                    && population(result) * 4 >= population(top)
+            })
+            .collect();
+        if strong.len() > 1 {
+            let listed: Vec<String> = strong
+                .iter()
+                .map(|result| {
+                    format!(
+                        "{} ({:.2}°, {:.2}°)",
+                        self.place_label(result),
+                        result
+                            .get("latitude")
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(0.0),
+                        result
+                            .get("longitude")
+                            .and_then(|v| v.as_f64())
+                            .unwrap_or(0.0)
+                    )
+                })
+                .collect();
+            return Err(format!(
+                "Location '{}' is ambiguous: {}. Add a region or country (e.g. '{}, {}') or pass latitude and longitude.",
+                query,
+                listed.join("; "),
+                name,
+                top.get("admin1")
+                    .or_else(|| top.get("country"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("Country")
             ));
         }
 
-        let data: Value = response.json().await?;
-        Ok(data)
+        match (
+            top.get("latitude").and_then(|v| v.as_f64()),
+            top.get("longitude").and_then(|v| v.as_f64()),
+        ) {
+            (Some(latitude), Some(longitude)) => Ok(Place {
+                latitude,
+                longitude,
+                label: Some(self.place_label(top)),
+            }),
+            _ => Err(format!(
+                "Geocoding result for '{}' has no coordinates.",
+                query
+            )),
+        }
     }
 
-    async fn fetch_weather_forecast(
-        &self,
-        latitude: f64,
-        longitude: f64,
-        days: u32,
-    ) -> Result<Value, anyhow::Error> {
-        let url = format!(
-            "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&daily=weather_code,temperature_2m_max,temperature_2m_min,apparent_temperature_max,apparent_temperature_min,sunrise,sunset,daylight_duration,sunshine_duration,uv_index_max,precipitation_sum,rain_sum,showers_sum,snowfall_sum,precipitation_hours,precipitation_probability_max,wind_speed_10m_max,wind_gusts_10m_max,wind_direction_10m_dominant,shortwave_radiation_sum&forecast_days={}",
-            latitude, longitude, days
-        );
+    /// 'Name, Admin1, Country' of a geocoding result, skipping missing or
+    /// repeated parts.
+    fn place_label(&self, result: &Value) -> String {
+        let mut parts: Vec<&str> = Vec::new();
+        for key in ["name", "admin1", "country"] {
+            if let Some(part) = result.get(key).and_then(|v| v.as_str()) {
+                if !part.is_empty() && !parts.contains(&part) {
+                    parts.push(part);
+                }
+            }
+        }
+        parts.join(", ")
+    }
 
-        let response = self.client.get(&url).send().await?;
+    /// Splits a multi-location response into one value per point; a single
+    /// location comes back as a plain object.
+    fn split_locations<'a>(&self, data: &'a Value) -> Vec<&'a Value> {
+        match data {
+            Value::Array(items) => items.iter().collect(),
+            single => vec![single],
+        }
+    }
 
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "OpenMeteo API error: {}",
-                response.status()
-            ));
+    fn validate_date(&self, date_str: &str) -> Result<NaiveDate, String> {
+        NaiveDate::parse_from_str(date_str, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date format: '{}'. Expected YYYY-MM-DD.", date_str))
+    }
+
+    fn validate_models(&self, models: &[String], catalog: &[(&str, &str)]) -> Result<(), String> {
+        for model in models {
+            if !catalog.iter().any(|(name, _)| name == model) {
+                let names: Vec<&str> = catalog.iter().map(|(name, _)| *name).collect();
+                return Err(format!(
+                    "Invalid model: '{}'. Must be one of: {}.",
+                    model,
+                    names.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_datetime(&self, datetime_str: &str) -> Result<NaiveDateTime, String> {
+        NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%dT%H:%M").map_err(|_| {
+            format!(
+                "Invalid time format: '{}'. Expected YYYY-MM-DDTHH:MM.",
+                datetime_str
+            )
+        })
+    }
+
+    fn validate_timezone(&self, timezone: &str) -> Result<(), String> {
+        if timezone == "auto" || timezone.parse::<chrono_tz::Tz>().is_ok() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid timezone: '{}'. Must be 'auto' or an IANA timezone name such as 'Europe/Paris'.",
+                timezone
+            ))
         }
+    }
 
-        let data: Value = response.json().await?;
-        Ok(data)
+    /// Lower-cased two-letter ISO 639-1 language code.
+    fn validate_language(&self, language: &str) -> Result<String, String> {
+        if language.len() == 2 && language.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(language.to_ascii_lowercase())
+        } else {
+            Err(format!(
+                "Invalid language: '{}'. Must be a two-letter ISO 639-1 code such as 'en' or 'de'.",
+                language
+            ))
+        }
+    }
+
+    /// Upper-cased two-letter ISO 3166-1 country code.
+    fn validate_country_code(&self, country_code: &str) -> Result<String, String> {
+        if country_code.len() == 2 && country_code.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(country_code.to_ascii_uppercase())
+        } else {
+            Err(format!(
+                "Invalid country code: '{}'. Must be a two-letter ISO 3166-1 code such as 'FR' or 'US'.",
+                country_code
+            ))
+        }
     }
 
-    async fn fetch_historical_weather(
+    fn validate_verification_request(
         &self,
-        latitude: f64,
-        longitude: f64,
         start_date: &str,
         end_date: &str,
-    ) -> Result<Value, anyhow::Error> {
-        let url = format!(
-            "https://api.open-meteo.com/v1/archive?latitude={}&longitude={}&start_date={}&end_date={}&daily=weather_code,temperature_2m_max,temperature_2m_min,temperature_2m_mean,apparent_temperature_max,apparent_temperature_min,apparent_temperature_mean,sunrise,sunset,daylight_duration,sunshine_duration,precipitation_sum,rain_sum,snowfall_sum,precipitation_hours,wind_speed_10m_max,wind_gusts_10m_max,wind_direction_10m_dominant",
-            latitude, longitude, start_date, end_date 
-        );
-
-        let response = self.client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "OpenMeteo API error: {}",
-                response.status()
+    ) -> Result<(), String> {
+        let start = self.validate_date(start_date)?;
+        let end = self.validate_date(end_date)?;
+        if end < start {
+            return Err(format!(
+                "Invalid date range: end date {} is before start date {}.",
+                end_date, start_date
             ));
         }
+        Ok(())
+    }
 
-        let data: Value = response.json().await?;
-        Ok(data)
+    /// Configured base URL of `api`, without a trailing slash.
+    fn base_url(&self, api: Api) -> String {
+        self.config.urls.get(api, self.config.api_key.is_some())
     }
 
-    async fn search_locations_helper(
-        &self,
-        query: &str,
-        limit: u32,
-    ) -> Result<Value, anyhow::Error> {
-        let url = format!(
-            "https://geocoding-api.open-meteo.com/v1/search?name={}&count={}&language=en&format=json",
-            urlencoding::encode(query), limit
-        );
-        tracing::debug!("Geocoding API URL: {}", url); // Log the URL
+    /// Sends a GET request, adding the API key when one is configured. The key
+    /// is never part of `url`, so logged URLs and cache keys stay free of it.
+    async fn send(&self, url: &str) -> Result<reqwest::Response, reqwest::Error> {
+        let mut request = self.client.get(url);
+        if let Some(key) = &self.config.api_key {
+            request = request.query(&[("apikey", key)]);
+        }
+        request.send().await.map_err(|e| redact_api_key(e, url))
+    }
 
-        let response = self.client.get(&url).send().await?;
+    /// GETs a JSON response, served from the response cache when possible.
+    /// `api` names the API in error messages.
+    async fn get_json(&self, url: &str, api: &str) -> Result<Value, anyhow::Error> {
+        if let Some(data) = self.cache.get(url) {
+            tracing::debug!("Cache hit: {}", url);
+            return Ok(data);
+        }
 
-        let status = response.status();
-        tracing::debug!("Geocoding API response status: {}", status);
+        let response = self.send(url).await?;
 
+        let status = response.status();
         if !status.is_success() {
             let err_text = response
                 .text()
                 .await
                 .unwrap_or_else(|_| "Failed to read error body".to_string());
-            tracing::error!(
-                "Geocoding API non-success. Status: {}. Body: {}",
-                status,
-                err_text
-            );
             return Err(anyhow::anyhow!(
-                "OpenMeteo Geocoding API error: {}. Body: {}",
+                "{} error: {}. Body: {}",
+                api,
                 status,
                 err_text
             ));
         }
 
-        let response_text = response.text().await?;
-        tracing::debug!("Geocoding API response text: {}", response_text);
-
-        let data: Value = serde_json::from_str(&response_text).map_err(|e| {
-            tracing::error!(
-                "Failed to parse Geocoding API JSON. Error: {}. Response text: {}",
-                e,
-                response_text
-            );
-            anyhow::anyhow!(
-                "Failed to parse Geocoding API JSON response: {}. Response text snippet: {:.200}",
-                e,
-                response_text
-            )
-        })?;
-
+        let data: Value = response.json().await.map_err(|e| redact_api_key(e, url))?;
+        self.cache.insert(url.to_string(), data.clone());
         Ok(data)
     }
 
-    fn format_current_weather(&self, data: &Value, latitude: f64, longitude: f64) -> String {
-        let current = data.get("current").unwrap_or(&Value::Null);
-        let current_units = data.get("current_units").unwrap_or(&Value::Null);
-
-        let temperature = current
-            .get("temperature_2m")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let temp_unit = current_units
-            .get("temperature_2m")
-            .and_then(|v| v.as_str())
-            .unwrap_or("°C");
-
-        let humidity = current
-            .get("relative_humidity_2m")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let humidity_unit = current_units
-            .get("relative_humidity_2m")
-            .and_then(|v| v.as_str())
-            .unwrap_or("%");
-
-        let feels_like = current
-            .get("apparent_temperature")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let precipitation = current
-            .get("precipitation")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let precip_unit = current_units
-            .get("precipitation")
-            .and_then(|v| v.as_str())
-            .unwrap_or("mm");
-
-        let wind_speed = current
-            .get("wind_speed_10m")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let wind_unit = current_units
-            .get("wind_speed_10m")
-            .and_then(|v| v.as_str())
-            .unwrap_or("km/h");
-        let wind_direction = current
-            .get("wind_direction_10m")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-
-        let pressure = current
-            .get("pressure_msl")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let pressure_unit = current_units
-            .get("pressure_msl")
-            .and_then(|v| v.as_str())
-            .unwrap_or("hPa");
-
-        let cloud_cover = current
-            .get("cloud_cover")
-            .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
-        let weather_code = current
-            .get("weather_code")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
-        let is_day = current.get("is_day").and_then(|v| v.as_u64()).unwrap_or(0) == 1;
-
-        let time = current
-            .get("time")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Unknown");
-
-        let weather_description = self.get_weather_description(weather_code, is_day);
-
-        format!(
-            "🌍 Current Weather\nLocation: {:.2}°, {:.2}°\nTime: {}\n\n🌡️ Temperature: {:.1}{}\n🤔 Feels like: {:.1}{}\n💧 Humidity: {:.0}{}\n☔ Precipitation: {:.1}{}\n💨 Wind: {:.1}{} from {}°\n🌫️ Cloud cover: {:.0}%\n📊 Pressure: {:.1}{}\n☀️ Conditions: {}",
-            latitude, longitude, time,
-            temperature, temp_unit,
-            feels_like, temp_unit,
-            humidity, humidity_unit,
-            precipitation, precip_unit,
-            wind_speed, wind_unit, wind_direction,
-            cloud_cover,
-            pressure, pressure_unit,
-            weather_description
-        )
+    /// Header suffix naming the nearest known place, when a GeoNames file is
+    /// loaded and a place lies within `NEAREST_PLACE_MAX_KM`.
+    fn format_nearest_place(&self, latitude: f64, longitude: f64) -> String {
+        let Some(cities) = &self.cities else {
+            return String::new();
+        };
+        match cities.nearest(latitude, longitude, 1).first() {
+            Some((city, distance)) if *distance <= NEAREST_PLACE_MAX_KM => {
+                format!(" (near {}, {})", city.name, city.country_code)
+            }
+            _ => String::new(),
+        }
     }
 
-    fn format_weather_forecast(
+    fn format_reverse_geocode(
         &self,
-        data: &Value,
         latitude: f64,
         longitude: f64,
-        days: u32,
+        places: &[(&geonames::City, f64)],
     ) -> String {
-        let daily = data.get("daily").unwrap_or(&Value::Null);
-        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
-
-        let empty_vec = vec![];
-        let dates = daily
-            .get("time")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let temp_max = daily
-            .get("temperature_2m_max")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let temp_min = daily
-            .get("temperature_2m_min")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let weather_codes = daily
-            .get("weather_code")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let precipitation = daily
-            .get("precipitation_sum")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let wind_speed = daily
-            .get("wind_speed_10m_max")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
+        if places.is_empty() {
+            return "No places found in the GeoNames file.".to_string();
+        }
 
-        let temp_unit = daily_units
-            .get("temperature_2m_max")
-            .and_then(|v| v.as_str())
-            .unwrap_or("°C");
-        let precip_unit = daily_units
-            .get("precipitation_sum")
-            .and_then(|v| v.as_str())
-            .unwrap_or("mm");
-        let wind_unit = daily_units
-            .get("wind_speed_10m_max")
-            .and_then(|v| v.as_str())
-            .unwrap_or("km/h");
-
-        let mut forecast = format!(
-            "🌍 {}-Day Weather Forecast\nLocation: {:.2}°, {:.2}°\n\n",
-            days, latitude, longitude
-        );
-
-        for i in 0..std::cmp::min(days as usize, dates.len()) {
-            let date = dates[i].as_str().unwrap_or("Unknown");
-            let max_temp = temp_max.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let min_temp = temp_min.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let code = weather_codes.get(i).and_then(|v| v.as_u64()).unwrap_or(0);
-            let precip = precipitation.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let wind = wind_speed.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
-
-            let weather_desc = self.get_weather_description(code, true); // Assume day for forecast
-
-            forecast.push_str(&format!(
-                "📅 {}\n🌡️ {:.1}{} / {:.1}{}\n☀️ {}\n☔ {:.1}{}\n💨 {:.1}{}\n\n",
-                date,
-                max_temp,
-                temp_unit,
-                min_temp,
-                temp_unit,
-                weather_desc,
-                precip,
-                precip_unit,
-                wind,
-                wind_unit
+        let mut report = format!(
+            "📍 Nearest Places to {:.4}°, {:.4}°:\n\n",
+            latitude, longitude
+        );
+        for (i, (city, distance)) in places.iter().enumerate() {
+            let direction = geonames::compass_point(geonames::bearing(
+                latitude,
+                longitude,
+                city.latitude,
+                city.longitude,
+            ));
+            let feature = match FEATURE_CODES
+                .iter()
+                .find(|(code, _)| *code == city.feature_code)
+            {
+                Some((code, description)) => format!("{} ({})", code, description),
+                None => city.feature_code.clone(),
+            };
+            report.push_str(&format!(
+                "{}. 📍 {} ({}) — {:.1} km {}\n📍 Coordinates: {:.4}°, {:.4}°\n🆔 GeoNames ID: {}\n🏷️ Feature: {}\n👥 Population: {}\n🕐 Timezone: {}\n\n",
+                i + 1,
+                city.name,
+                city.country_code,
+                distance,
+                direction,
+                city.latitude,
+                city.longitude,
+                city.id,
+                feature,
+                city.population,
+                city.timezone
             ));
         }
-
-        forecast
+        report
     }
 
-    fn format_historical_weather(
-        &self,
-        data: &Value,
-        latitude: f64,
-        longitude: f64,
-        start_date: &str,
-        end_date: &str,
-    ) -> String {
-        let daily = data.get("daily").unwrap_or(&Value::Null);
-        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+    /// Header suffix with the elevation of the grid cell the data refers to.
+    fn format_elevation(&self, data: &Value) -> String {
+        match data.get("elevation").and_then(|v| v.as_f64()) {
+            Some(elevation) => format!(" (grid elevation: {:.0}m)", elevation),
+            None => String::new(),
+        }
+    }
 
-        let empty_vec = vec![];
-        let dates = daily
-            .get("time")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let temp_max = daily
-            .get("temperature_2m_max")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let temp_min = daily
-            .get("temperature_2m_min")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let temp_mean = daily
-            .get("temperature_2m_mean")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-        let precipitation = daily
-            .get("precipitation_sum")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
+    /// Current date in the timezone of a response.
+    fn local_today(&self, data: &Value) -> NaiveDate {
+        let utc_offset = data
+            .get("utc_offset_seconds")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        (chrono::Utc::now() + chrono::Duration::seconds(utc_offset)).date_naive()
+    }
 
-        let temp_unit = daily_units
-            .get("temperature_2m_max")
-            .and_then(|v| v.as_str())
-            .unwrap_or("°C");
-        let precip_unit = daily_units
-            .get("precipitation_sum")
+    fn format_timezone(&self, data: &Value) -> String {
+        let Some(timezone) = data.get("timezone").and_then(|v| v.as_str()) else {
+            return String::new();
+        };
+        let offset = data
+            .get("utc_offset_seconds")
+            .and_then(|v| v.as_i64())
+            .unwrap_or(0);
+        let abbreviation = data
+            .get("timezone_abbreviation")
             .and_then(|v| v.as_str())
-            .unwrap_or("mm");
-
-        let mut history = format!(
-            "🌍 Historical Weather Data\nLocation: {:.2}°, {:.2}°\nPeriod: {} to {}\n\n",
-            latitude, longitude, start_date, end_date
-        );
-
-        let mut total_temp_max = 0.0;
-        let mut total_temp_min = 0.0;
-        let mut total_temp_mean = 0.0;
-        let mut total_precip = 0.0;
-        let mut count = 0;
-
-        for i in 0..dates.len() {
-            if let (Some(max_temp), Some(min_temp), Some(mean_temp), Some(precip)) = (
-                temp_max.get(i).and_then(|v| v.as_f64()),
-                temp_min.get(i).and_then(|v| v.as_f64()),
-                temp_mean.get(i).and_then(|v| v.as_f64()),
-                precipitation.get(i).and_then(|v| v.as_f64()),
-            ) {
-                total_temp_max += max_temp;
-                total_temp_min += min_temp;
-                total_temp_mean += mean_temp;
-                total_precip += precip;
-                count += 1;
-            }
-        }
-
-        if count > 0 {
-            history.push_str(&format!(
-                "📊 Summary Statistics ({} days):\n🌡️ Average High: {:.1}{}\n🌡️ Average Low: {:.1}{}\n🌡️ Average Mean: {:.1}{}\n☔ Total Precipitation: {:.1}{}\n☔ Average Daily Precipitation: {:.1}{}\n\n",
-                count,
-                total_temp_max / count as f64, temp_unit,
-                total_temp_min / count as f64, temp_unit,
-                total_temp_mean / count as f64, temp_unit,
-                total_precip, precip_unit,
-                total_precip / count as f64, precip_unit
-            ));
-        }
+            .filter(|abbreviation| *abbreviation != timezone)
+            .map(|abbreviation| format!("{}, ", abbreviation))
+            .unwrap_or_default();
+        format!(
+            "\nTimezone: {} ({}UTC{}{:02}:{:02})",
+            timezone,
+            abbreviation,
+            if offset < 0 { '-' } else { '+' },
+            offset.abs() / 3600,
+            offset.abs() % 3600 / 60
+        )
+    }
 
-        history.push_str("📅 Daily Data (first 5 days):\n");
-        for i in 0..std::cmp::min(5, dates.len()) {
-            let date = dates[i].as_str().unwrap_or("Unknown");
-            let max_temp = temp_max.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let min_temp = temp_min.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
-            let precip = precipitation.get(i).and_then(|v| v.as_f64()).unwrap_or(0.0);
+    fn model_label<'a>(&self, model: &'a str) -> &'a str {
+        FORECAST_MODELS
+            .iter()
+            .chain(ARCHIVE_MODELS.iter())
+            .find(|(name, _)| *name == model)
+            .map(|(_, label)| *label)
+            .unwrap_or(model)
+    }
 
-            history.push_str(&format!(
-                "{}: {:.1}{} / {:.1}{}, {:.1}{}\n",
-                date, max_temp, temp_unit, min_temp, temp_unit, precip, precip_unit
-            ));
+    /// Inserts a model line below the title of a single-model report.
+    fn with_model_header(&self, report: String, models: &[String]) -> String {
+        match models {
+            [model] => report.replacen('\n', &format!("\nModel: {}\n", self.model_label(model)), 1),
+            _ => report,
         }
-
-        history
     }
 
-    fn format_locations(&self, data: &Value) -> String {
-        let empty_vec = vec![];
-        let results = data
-            .get("results")
-            .and_then(|v| v.as_array())
-            .unwrap_or(&empty_vec);
-
-        if results.is_empty() {
-            return "No locations found matching your search query.".to_string();
+    /// Appends generic tables for explicitly requested variables, which the
+    /// bespoke formatters may not know about. Tables cover at most `max_days`
+    /// days and `MAX_HOURLY_TABLE_ROWS` hours.
+    fn with_variable_tables(
+        &self,
+        report: String,
+        data: &Value,
+        variables: &[Variable],
+        models: &[String],
+        max_days: usize,
+    ) -> String {
+        if variables.is_empty() {
+            return report;
         }
 
-        let mut locations = "🌍 Location Search Results:\n\n".to_string();
-
-        for (i, result) in results.iter().enumerate() {
-            let name = result
-                .get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown");
-            let country = result
-                .get("country")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown");
-            let admin1 = result.get("admin1").and_then(|v| v.as_str());
-            let latitude = result
-                .get("latitude")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0);
-            let longitude = result
-                .get("longitude")
-                .and_then(|v| v.as_f64())
-                .unwrap_or(0.0);
-            let timezone = result
-                .get("timezone")
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown");
-            let population = result.get("population").and_then(|v| v.as_u64());
+        let fmt_value = |value: Option<&Value>| match value {
+            Some(Value::String(text)) => text.clone(),
+            Some(Value::Null) | None => "n/a".to_string(),
+            Some(value) => value.to_string(),
+        };
+
+        let mut report = report.trim_end().to_string();
+        for block in [Block::Current, Block::Daily, Block::Hourly] {
+            // Several models come back as "<variable>_<model>" columns.
+            let columns: Vec<String> = variables
+                .iter()
+                .filter(|variable| variable.block == block)
+                .flat_map(|variable| {
+                    if models.len() > 1 {
+                        models
+                            .iter()
+                            .map(|model| format!("{}_{}", variable.name, model))
+                            .collect()
+                    } else {
+                        vec![variable.name.to_string()]
+                    }
+                })
+                .collect();
+            if columns.is_empty() {
+                continue;
+            }
 
-            let admin_info = if let Some(admin) = admin1 {
-                format!(", {}", admin)
-            } else {
-                String::new()
+            let values = data.get(block.key()).unwrap_or(&Value::Null);
+            let block_units = data.get(block.units_key()).unwrap_or(&Value::Null);
+            let unit = |column: &str| {
+                block_units
+                    .get(column)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string()
             };
 
-            let pop_info = if let Some(pop) = population {
-                format!("\n👥 Population: {}", pop)
-            } else {
-                String::new()
-            };
+            report.push_str(&format!("\n\n📋 Requested {} variables:\n", block.key()));
+            if block == Block::Current {
+                for column in &columns {
+                    report.push_str(&format!(
+                        "• {}: {}{}\n",
+                        column,
+                        fmt_value(values.get(column)),
+                        unit(column)
+                    ));
+                }
+                continue;
+            }
 
-            locations.push_str(&format!(
-                "{}. 📍 {}{}, {}\n📍 Coordinates: {:.4}°, {:.4}°\n🕐 Timezone: {}{}\n\n",
-                i + 1,
-                name,
-                admin_info,
-                country,
-                latitude,
-                longitude,
-                timezone,
-                pop_info
-            ));
+            let header: Vec<String> = columns
+                .iter()
+                .map(|column| match unit(column).as_str() {
+                    "" => column.clone(),
+                    unit => format!("{} ({})", column, unit),
+                })
+                .collect();
+            report.push_str(&format!("Time | {}\n", header.join(" | ")));
+
+            let empty_vec = vec![];
+            let times = values
+                .get("time")
+                .and_then(|v| v.as_array())
+                .unwrap_or(&empty_vec);
+            let max_rows = match block {
+                Block::Daily => max_days,
+                _ => max_days.saturating_mul(24).min(MAX_HOURLY_TABLE_ROWS),
+            };
+            for (i, time) in times.iter().enumerate().take(max_rows) {
+                let row: Vec<String> = columns
+                    .iter()
+                    .map(|column| fmt_value(values.get(column).and_then(|series| series.get(i))))
+                    .collect();
+                report.push_str(&format!(
+                    "{} | {}\n",
+                    fmt_value(Some(time)),
+                    row.join(" | ")
+                ));
+            }
+            if times.len() > max_rows {
+                report.push_str(&format!(
+                    "… showing the first {} of {} rows\n",
+                    max_rows,
+                    times.len()
+                ));
+            }
         }
 
-        locations
+        report
+    }
+
+    /// Returns true when the given series of the response block holds at least
+    /// one non-null value. The marine API answers with all-null series for
+    /// grid cells that are on land.
+    fn has_series_data(&self, block: &Value, key: &str) -> bool {
+        block
+            .get(key)
+            .and_then(|v| v.as_array())
+            .is_some_and(|values| values.iter().any(|v| !v.is_null()))
+    }
+
+    fn daily_series(&self, data: &Value, key: &str) -> DailySeries {
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let empty_vec = vec![];
+        let dates = daily
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let values = daily
+            .get(key)
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        dates
+            .iter()
+            .zip(values)
+            .filter_map(|(date, value)| Some((date.as_str()?.to_string(), value.as_f64()?)))
+            .collect()
     }
 
     fn get_weather_description(&self, code: u64, _is_day: bool) -> &'static str {
@@ -586,25 +1408,83 @@ impl OpenMeteoServer {
         &self,
         #[tool(aggr)] params: GetCurrentWeatherParams,
     ) -> Result<CallToolResult, McpError> {
+        let models = params.models.clone().unwrap_or_default();
+
         tracing::info!(
-            latitude = %params.latitude,
-            longitude = %params.longitude,
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            locations = ?params.locations,
+            models = ?models,
+            variables = ?params.variables,
+            timezone = %params.timezone.name(),
             "Getting current weather"
         );
 
-        if let Err(err) = self.validate_coordinates(params.latitude, params.longitude) {
-            tracing::error!("Invalid coordinates: {}", err);
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let variables = match variables::resolve(
+            params.variables.as_deref().unwrap_or(&[]),
+            &[Block::Current],
+            Source::Forecast,
+        ) {
+            Ok(variables) => variables,
+            Err(err) => {
+                tracing::error!("Invalid variables: {}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        };
+
+        if let Err(err) = self.validate_models(&models, &FORECAST_MODELS) {
+            tracing::error!("Invalid models: {}", err);
             return Ok(CallToolResult::error(vec![Content::text(err)]));
         }
 
-
-        match self
-            .fetch_current_weather(params.latitude, params.longitude)
+        let places = match self
+            .resolve_locations(&params.place, params.locations.as_deref())
             .await
         {
+            Ok(places) => places,
+            Err(result) => return Ok(result),
+        };
+        let locations: Vec<Coordinates> = places.iter().map(Place::coordinates).collect();
+
+        let options = QueryOptions {
+            models: models.clone(),
+            variables: variables.clone(),
+            ..options
+        };
+
+        match self.fetch_current_weather(&locations, &options).await {
             Ok(data) => {
-                let formatted =
-                    self.format_current_weather(&data, params.latitude, params.longitude);
+                let sections: Vec<String> = self
+                    .split_locations(&data)
+                    .into_iter()
+                    .zip(&places)
+                    .map(|(data, place)| {
+                        let formatted = if models.len() > 1 {
+                            self.format_model_comparison(
+                                data,
+                                "Current Weather",
+                                place,
+                                &models,
+                                0,
+                                units,
+                            )
+                        } else {
+                            self.with_model_header(
+                                self.format_current_weather(data, place, units),
+                                &models,
+                            )
+                        };
+                        self.with_variable_tables(formatted, data, &variables, &models, 1)
+                    })
+                    .collect();
+                let formatted = sections.join("\n\n");
                 tracing::info!("Successfully retrieved current weather");
                 Ok(CallToolResult::success(vec![Content::text(formatted)]))
             }
@@ -624,32 +1504,1128 @@ impl OpenMeteoServer {
         &self,
         #[tool(aggr)] params: GetWeatherForecastParams,
     ) -> Result<CallToolResult, McpError> {
-        let days = params.days.unwrap_or(7).clamp(1, 16);
+        let days = params
+            .days
+            .unwrap_or(self.config.forecast_days)
+            .clamp(1, 16);
+        let models = params.models.clone().unwrap_or_default();
 
         tracing::info!(
-            latitude = %params.latitude,
-            longitude = %params.longitude,
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            locations = ?params.locations,
             days = %days,
+            models = ?models,
+            variables = ?params.variables,
+            timezone = %params.timezone.name(),
             "Getting weather forecast"
         );
 
-        if let Err(err) = self.validate_coordinates(params.latitude, params.longitude) {
-            tracing::error!("Invalid coordinates: {}", err);
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let variables = match variables::resolve(
+            params.variables.as_deref().unwrap_or(&[]),
+            &[Block::Daily, Block::Hourly],
+            Source::Forecast,
+        ) {
+            Ok(variables) => variables,
+            Err(err) => {
+                tracing::error!("Invalid variables: {}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        };
+
+        if let Err(err) = self.validate_models(&models, &FORECAST_MODELS) {
+            tracing::error!("Invalid models: {}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let places = match self
+            .resolve_locations(&params.place, params.locations.as_deref())
+            .await
+        {
+            Ok(places) => places,
+            Err(result) => return Ok(result),
+        };
+        let locations: Vec<Coordinates> = places.iter().map(Place::coordinates).collect();
+
+        let options = QueryOptions {
+            models: models.clone(),
+            variables: variables.clone(),
+            ..options
+        };
+
+        match self
+            .fetch_weather_forecast(&locations, days, &options)
+            .await
+        {
+            Ok(data) => {
+                let sections: Vec<String> = self
+                    .split_locations(&data)
+                    .into_iter()
+                    .zip(&places)
+                    .map(|(data, place)| {
+                        let formatted = if models.len() > 1 {
+                            self.format_model_comparison(
+                                data,
+                                &format!("{}-Day Weather Forecast", days),
+                                place,
+                                &models,
+                                days as usize,
+                                units,
+                            )
+                        } else {
+                            self.with_model_header(
+                                self.format_weather_forecast(data, place, days, units),
+                                &models,
+                            )
+                        };
+                        self.with_variable_tables(
+                            formatted,
+                            data,
+                            &variables,
+                            &models,
+                            days as usize,
+                        )
+                    })
+                    .collect();
+                let formatted = sections.join("\n\n");
+                tracing::info!("Successfully retrieved weather forecast for {} days", days);
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving weather forecast: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_hourly_forecast",
+        description = "Get an hour-by-hour weather forecast for a specific location. Returns temperature, precipitation probability and amount, weather conditions, wind, cloud cover, and visibility for up to 384 hours, grouped by day."
+    )]
+    async fn get_hourly_forecast(
+        &self,
+        #[tool(aggr)] params: GetHourlyForecastParams,
+    ) -> Result<CallToolResult, McpError> {
+        let hours = params.hours.unwrap_or(24).clamp(1, 384);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            hours = %hours,
+            start_time = ?params.start_time,
+            timezone = %params.timezone.name(),
+            "Getting hourly forecast"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let start_time = match params
+            .start_time
+            .as_deref()
+            .map(|s| self.validate_datetime(s))
+        {
+            Some(Ok(start)) => Some(start),
+            Some(Err(err)) => {
+                tracing::error!("Invalid start time: {}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+            None => None,
+        };
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        match self
+            .fetch_hourly_forecast(place.latitude, place.longitude, hours, start_time, &options)
+            .await
+        {
+            Ok(data) => {
+                let formatted = self.format_hourly_forecast(&data, &place, hours, units);
+                tracing::info!("Successfully retrieved hourly forecast for {} hours", hours);
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving hourly forecast: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_historical_weather",
+        description = "Get historical weather data for a specific location and date range. Returns daily weather statistics including temperature, precipitation, and other meteorological data for analysis."
+    )]
+    async fn get_historical_weather(
+        &self,
+        #[tool(aggr)] params: GetHistoricalWeatherParams,
+    ) -> Result<CallToolResult, McpError> {
+        let models = params.models.clone().unwrap_or_default();
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            locations = ?params.locations,
+            start_date = %params.start_date,
+            end_date = %params.end_date,
+            models = ?models,
+            variables = ?params.variables,
+            timezone = %params.timezone.name(),
+            "Getting historical weather"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let variables = match variables::resolve(
+            params.variables.as_deref().unwrap_or(&[]),
+            &[Block::Daily, Block::Hourly],
+            Source::Archive,
+        ) {
+            Ok(variables) => variables,
+            Err(err) => {
+                tracing::error!("Invalid variables: {}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        };
+
+        if let Err(err) = self.validate_date(&params.start_date) {
+            tracing::error!("Invalid start date: {}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        if let Err(err) = self.validate_date(&params.end_date) {
+            tracing::error!("Invalid end date: {}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        if let Err(err) = self.validate_models(&models, &ARCHIVE_MODELS) {
+            tracing::error!("Invalid models: {}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let places = match self
+            .resolve_locations(&params.place, params.locations.as_deref())
+            .await
+        {
+            Ok(places) => places,
+            Err(result) => return Ok(result),
+        };
+        let locations: Vec<Coordinates> = places.iter().map(Place::coordinates).collect();
+
+        let options = QueryOptions {
+            models: models.clone(),
+            variables: variables.clone(),
+            ..options
+        };
+
+        match self
+            .fetch_historical_weather(&locations, &params.start_date, &params.end_date, &options)
+            .await
+        {
+            Ok(data) => {
+                let sections: Vec<String> = self
+                    .split_locations(&data)
+                    .into_iter()
+                    .zip(&places)
+                    .map(|(data, place)| {
+                        let formatted = if models.len() > 1 {
+                            self.format_model_comparison(
+                                data,
+                                &format!(
+                                    "Historical Weather Data ({} to {})",
+                                    params.start_date, params.end_date
+                                ),
+                                place,
+                                &models,
+                                5,
+                                units,
+                            )
+                        } else {
+                            self.with_model_header(
+                                self.format_historical_weather(
+                                    data,
+                                    place,
+                                    &params.start_date,
+                                    &params.end_date,
+                                    units,
+                                ),
+                                &models,
+                            )
+                        };
+                        self.with_variable_tables(formatted, data, &variables, &models, usize::MAX)
+                    })
+                    .collect();
+                let formatted = sections.join("\n\n");
+                tracing::info!("Successfully retrieved historical weather data");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving historical weather: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_air_quality",
+        description = "Get current air quality for a specific location. Returns European and US AQI, PM2.5, PM10, ozone, nitrogen dioxide and other pollutants, plus pollen counts (Europe only), each with a category label such as 'Good' or 'Unhealthy'."
+    )]
+    async fn get_air_quality(
+        &self,
+        #[tool(aggr)] params: GetAirQualityParams,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            timezone = %params.timezone.name(),
+            "Getting air quality"
+        );
+
+        let options = match self.resolve_options(None, &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        match self
+            .fetch_air_quality(place.latitude, place.longitude, &options)
+            .await
+        {
+            Ok(data) => {
+                let formatted = self.format_air_quality(&data, &place);
+                tracing::info!("Successfully retrieved air quality");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving air quality: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_marine_forecast",
+        description = "Get a marine forecast for a coastal or offshore location. Returns wave height, direction, and period, swell and wind-wave components, and sea surface temperature, either daily (default) or hourly for up to 16 days."
+    )]
+    async fn get_marine_forecast(
+        &self,
+        #[tool(aggr)] params: GetMarineForecastParams,
+    ) -> Result<CallToolResult, McpError> {
+        let hourly = match params.resolution.as_deref().unwrap_or("daily") {
+            "daily" => false,
+            "hourly" => true,
+            other => {
+                let err = format!(
+                    "Invalid resolution: '{}'. Must be 'daily' or 'hourly'.",
+                    other
+                );
+                tracing::error!("{}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        };
+        let days = params
+            .days
+            .unwrap_or(if hourly { 1 } else { 7 })
+            .clamp(1, 16);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            hourly = %hourly,
+            days = %days,
+            timezone = %params.timezone.name(),
+            "Getting marine forecast"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        match self
+            .fetch_marine_forecast(place.latitude, place.longitude, hourly, days, &options)
+            .await
+        {
+            Ok(data) => {
+                let (block, key) = if hourly {
+                    ("hourly", "wave_height")
+                } else {
+                    ("daily", "wave_height_max")
+                };
+                let block = data.get(block).unwrap_or(&Value::Null);
+                if !self.has_series_data(block, key)
+                    && !self.has_series_data(
+                        data.get("hourly").unwrap_or(&Value::Null),
+                        "sea_surface_temperature",
+                    )
+                {
+                    let err_msg = format!(
+                        "No marine data available for {:.2}°, {:.2}°. The location appears to be inland; choose a coastal or offshore point.",
+                        place.latitude, place.longitude
+                    );
+                    tracing::error!("{}", err_msg);
+                    return Ok(CallToolResult::error(vec![Content::text(err_msg)]));
+                }
+
+                let formatted = self.format_marine_forecast(&data, &place, hourly, days, units);
+                tracing::info!("Successfully retrieved marine forecast for {} days", days);
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving marine forecast: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_river_discharge",
+        description = "Get daily river discharge for the river nearest to a location from the GloFAS flood model. Returns past values and an ensemble forecast (mean, median, max, 25th/75th percentiles) for up to 210 days, flagging days well above the ensemble median."
+    )]
+    async fn get_river_discharge(
+        &self,
+        #[tool(aggr)] params: GetRiverDischargeParams,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            forecast_days = ?params.forecast_days,
+            past_days = ?params.past_days,
+            start_date = ?params.start_date,
+            end_date = ?params.end_date,
+            timezone = %params.timezone.name(),
+            "Getting river discharge"
+        );
+
+        let options = match self.resolve_options(None, &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+
+        let window = match (&params.start_date, &params.end_date) {
+            (Some(start_date), Some(end_date)) => {
+                let start = match self.validate_date(start_date) {
+                    Ok(date) => date,
+                    Err(err) => {
+                        tracing::error!("Invalid start date: {}", err);
+                        return Ok(CallToolResult::error(vec![Content::text(err)]));
+                    }
+                };
+                let end = match self.validate_date(end_date) {
+                    Ok(date) => date,
+                    Err(err) => {
+                        tracing::error!("Invalid end date: {}", err);
+                        return Ok(CallToolResult::error(vec![Content::text(err)]));
+                    }
+                };
+                if end < start {
+                    let err = format!(
+                        "Invalid date range: end date {} is before start date {}.",
+                        end_date, start_date
+                    );
+                    tracing::error!("{}", err);
+                    return Ok(CallToolResult::error(vec![Content::text(err)]));
+                }
+                DischargeWindow::Dates {
+                    start_date: start_date.clone(),
+                    end_date: end_date.clone(),
+                }
+            }
+            (None, None) => DischargeWindow::Relative {
+                past_days: params.past_days.unwrap_or(7).min(92),
+                forecast_days: params.forecast_days.unwrap_or(92).clamp(1, 210),
+            },
+            _ => {
+                let err = "Both 'start_date' and 'end_date' must be provided together.".to_string();
+                tracing::error!("{}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        };
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        match self
+            .fetch_river_discharge(place.latitude, place.longitude, &window, &options)
+            .await
+        {
+            Ok(data) => {
+                let formatted = self.format_river_discharge(&data, &place);
+                tracing::info!("Successfully retrieved river discharge");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving river discharge: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_ensemble_forecast",
+        description = "Get an ensemble forecast for a specific location to quantify forecast uncertainty. Returns per-day minimum, median, and maximum across ensemble members for a chosen model (ICON-EPS, GFS, ECMWF IFS, GEM) and, if a threshold is given, the probability of exceeding it."
+    )]
+    async fn get_ensemble_forecast(
+        &self,
+        #[tool(aggr)] params: GetEnsembleForecastParams,
+    ) -> Result<CallToolResult, McpError> {
+        let model_name = params.model.as_deref().unwrap_or("icon_eps");
+        let Some((_, model, model_label)) = ENSEMBLE_MODELS
+            .iter()
+            .find(|(name, api_name, _)| *name == model_name || *api_name == model_name)
+        else {
+            let names: Vec<&str> = ENSEMBLE_MODELS.iter().map(|(name, _, _)| *name).collect();
+            let err = format!(
+                "Invalid ensemble model: '{}'. Must be one of: {}.",
+                model_name,
+                names.join(", ")
+            );
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        };
+
+        let variable_name = params.variable.as_deref().unwrap_or("precipitation");
+        let Some(variable) = EnsembleVariable::parse(variable_name) else {
+            let err = format!(
+                "Invalid variable: '{}'. Must be one of: precipitation, temperature_max, temperature_min, wind_speed_max.",
+                variable_name
+            );
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        };
+
+        let days = params.days.unwrap_or(7).clamp(1, 35);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            model = %model,
+            variable = %variable_name,
+            threshold = ?params.threshold,
+            days = %days,
+            timezone = %params.timezone.name(),
+            "Getting ensemble forecast"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        let options = QueryOptions {
+            models: vec![model.to_string()],
+            ..options
+        };
+
+        match self
+            .fetch_ensemble_forecast(place.latitude, place.longitude, variable, days, &options)
+            .await
+        {
+            Ok(data) => {
+                let formatted = self.format_ensemble_forecast(
+                    &data,
+                    &place,
+                    model_label,
+                    variable,
+                    params.threshold,
+                    units,
+                );
+                tracing::info!("Successfully retrieved ensemble forecast for {} days", days);
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving ensemble forecast: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_climate_projection",
+        description = "Get downscaled CMIP6 climate-change projections for a specific location. Returns annual, decadal, and seasonal aggregates of temperature and precipitation for each requested climate model between 1950 and 2050."
+    )]
+    async fn get_climate_projection(
+        &self,
+        #[tool(aggr)] params: GetClimateProjectionParams,
+    ) -> Result<CallToolResult, McpError> {
+        let models: Vec<String> = params.models.clone().unwrap_or_else(|| {
+            DEFAULT_CLIMATE_MODELS
+                .iter()
+                .map(|m| m.to_string())
+                .collect()
+        });
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            models = ?models,
+            start_year = %params.start_year,
+            end_year = %params.end_year,
+            timezone = %params.timezone.name(),
+            "Getting climate projection"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        if models.is_empty() {
+            let err = "At least one climate model must be given.".to_string();
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+        if let Some(unknown) = models
+            .iter()
+            .find(|m| !CLIMATE_MODELS.contains(&m.as_str()))
+        {
+            let err = format!(
+                "Invalid climate model: '{}'. Must be one of: {}.",
+                unknown,
+                CLIMATE_MODELS.join(", ")
+            );
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        if !(1950..=2050).contains(&params.start_year)
+            || !(1950..=2050).contains(&params.end_year)
+            || params.start_year > params.end_year
+        {
+            let err = format!(
+                "Invalid year range: {} to {}. Years must be between 1950 and 2050, start before end.",
+                params.start_year, params.end_year
+            );
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        let options = QueryOptions {
+            models: models.clone(),
+            ..options
+        };
+
+        match self
+            .fetch_climate_projection(
+                place.latitude,
+                place.longitude,
+                params.start_year,
+                params.end_year,
+                &options,
+            )
+            .await
+        {
+            Ok(data) => {
+                let formatted = self.format_climate_projection(
+                    &data,
+                    &place,
+                    &models,
+                    params.start_year,
+                    params.end_year,
+                    units,
+                );
+                tracing::info!("Successfully retrieved climate projection");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving climate projection: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_seasonal_forecast",
+        description = "Get a long-range seasonal forecast for a specific location, up to 9 months ahead. Returns weekly or monthly ensemble-mean temperature and precipitation with anomalies relative to the 1991-2020 climate normal."
+    )]
+    async fn get_seasonal_forecast(
+        &self,
+        #[tool(aggr)] params: GetSeasonalForecastParams,
+    ) -> Result<CallToolResult, McpError> {
+        let weekly = match params.period.as_deref().unwrap_or("monthly") {
+            "monthly" => false,
+            "weekly" => true,
+            other => {
+                let err = format!(
+                    "Invalid period: '{}'. Must be 'monthly' or 'weekly'.",
+                    other
+                );
+                tracing::error!("{}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        };
+        let months = params.months.unwrap_or(6).clamp(1, 9);
+        let forecast_days = (months * 31).min(MAX_SEASONAL_FORECAST_DAYS);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            months = %months,
+            weekly = %weekly,
+            timezone = %params.timezone.name(),
+            "Getting seasonal forecast"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        let (forecast, normals) = tokio::join!(
+            self.fetch_seasonal_forecast(place.latitude, place.longitude, forecast_days, &options),
+            self.climate_normals(place.latitude, place.longitude, &options)
+        );
+
+        match forecast.and_then(|data| normals.map(|normals| (data, normals))) {
+            Ok((data, normals)) => {
+                let formatted =
+                    self.format_seasonal_forecast(&data, &normals, &place, weekly, units);
+                tracing::info!(
+                    "Successfully retrieved seasonal forecast for {} months",
+                    months
+                );
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving seasonal forecast: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_elevation",
+        description = "Get the terrain elevation for one or more locations from a 90m digital elevation model. Accepts up to 100 points per request."
+    )]
+    async fn get_elevation(
+        &self,
+        #[tool(aggr)] params: GetElevationParams,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!(points = %params.locations.len(), "Getting elevation");
+
+        if params.locations.is_empty() || params.locations.len() > 100 {
+            let err = format!(
+                "Invalid number of locations: {}. Must be between 1 and 100.",
+                params.locations.len()
+            );
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        for (i, location) in params.locations.iter().enumerate() {
+            if let Err(err) = self.validate_coordinates(location.latitude, location.longitude) {
+                let err = format!("Location {}: {}", i + 1, err);
+                tracing::error!("Invalid coordinates: {}", err);
+                return Ok(CallToolResult::error(vec![Content::text(err)]));
+            }
+        }
+
+        match self.fetch_elevation(&params.locations).await {
+            Ok(data) => {
+                let formatted = self.format_elevations(&data, &params.locations);
+                tracing::info!("Successfully retrieved elevation");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving elevation: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_solar_radiation",
+        description = "Get a solar radiation forecast for PV planning at a specific location. Returns daily global horizontal, direct, diffuse, direct normal, and tilted-plane irradiation in kWh/m² and, given a system size, an estimated PV energy yield."
+    )]
+    async fn get_solar_radiation(
+        &self,
+        #[tool(aggr)] params: GetSolarRadiationParams,
+    ) -> Result<CallToolResult, McpError> {
+        let tilt = params.tilt.unwrap_or(30.0);
+        let azimuth = params.azimuth.unwrap_or(0.0);
+        let efficiency = params.efficiency.unwrap_or(0.8);
+        let days = params.days.unwrap_or(7).clamp(1, 16);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            tilt = %tilt,
+            azimuth = %azimuth,
+            system_size_kwp = ?params.system_size_kwp,
+            efficiency = %efficiency,
+            days = %days,
+            timezone = %params.timezone.name(),
+            "Getting solar radiation"
+        );
+
+        let options = match self.resolve_options(None, &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+
+        let invalid = if !(0.0..=90.0).contains(&tilt) {
+            Some(format!("Invalid tilt: {}. Must be between 0 and 90.", tilt))
+        } else if !(-180.0..=180.0).contains(&azimuth) {
+            Some(format!(
+                "Invalid azimuth: {}. Must be between -180 and 180.",
+                azimuth
+            ))
+        } else if !(efficiency > 0.0 && efficiency <= 1.0) {
+            Some(format!(
+                "Invalid efficiency: {}. Must be between 0 and 1.",
+                efficiency
+            ))
+        } else if params.system_size_kwp.is_some_and(|size| size <= 0.0) {
+            Some("Invalid system size: must be greater than 0 kWp.".to_string())
+        } else {
+            None
+        };
+        if let Some(err) = invalid {
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        match self
+            .fetch_solar_radiation(
+                place.latitude,
+                place.longitude,
+                tilt,
+                azimuth,
+                days,
+                &options,
+            )
+            .await
+        {
+            Ok(data) => {
+                let formatted = self.format_solar_radiation(
+                    &data,
+                    &place,
+                    tilt,
+                    azimuth,
+                    params.system_size_kwp,
+                    efficiency,
+                );
+                tracing::info!("Successfully retrieved solar radiation for {} days", days);
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving solar radiation: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_historical_forecast",
+        description = "Verify archived weather forecasts for a specific location and date range against the historical archive. Returns bias and mean absolute error for daily max/min temperature, precipitation, and max wind speed, plus daily forecast/observed pairs."
+    )]
+    async fn get_historical_forecast(
+        &self,
+        #[tool(aggr)] params: GetHistoricalForecastParams,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            start_date = %params.start_date,
+            end_date = %params.end_date,
+            timezone = %params.timezone.name(),
+            "Getting historical forecast verification"
+        );
+
+        if let Err(err) = self.validate_verification_request(&params.start_date, &params.end_date) {
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        let location = [place.coordinates()];
+        let (forecast, archive) = tokio::join!(
+            self.fetch_historical_forecast(
+                place.latitude,
+                place.longitude,
+                &params.start_date,
+                &params.end_date,
+                &options,
+            ),
+            self.fetch_historical_weather(
+                &location,
+                &params.start_date,
+                &params.end_date,
+                &options,
+            )
+        );
+
+        match forecast.and_then(|data| archive.map(|archive| (data, archive))) {
+            Ok((data, archive)) => {
+                let forecast: Vec<DailySeries> = VERIFICATION_VARIABLES
+                    .iter()
+                    .map(|(key, _)| self.daily_series(&data, key))
+                    .collect();
+                let formatted = self.format_verification(
+                    "Historical Forecast Verification",
+                    &place,
+                    &forecast,
+                    &archive,
+                    units,
+                );
+                tracing::info!("Successfully verified historical forecast");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving historical forecast: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_previous_model_runs",
+        description = "Verify what the forecast model predicted a given number of days in advance (e.g. 'what was forecast for yesterday 3 days ago') against the historical archive. Returns bias and mean absolute error for daily max/min temperature, precipitation, and max wind speed."
+    )]
+    async fn get_previous_model_runs(
+        &self,
+        #[tool(aggr)] params: GetPreviousModelRunsParams,
+    ) -> Result<CallToolResult, McpError> {
+        let lead_days = params.lead_days.unwrap_or(3).clamp(1, 7);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            start_date = %params.start_date,
+            end_date = %params.end_date,
+            lead_days = %lead_days,
+            timezone = %params.timezone.name(),
+            "Getting previous model runs verification"
+        );
+
+        if let Err(err) = self.validate_verification_request(&params.start_date, &params.end_date) {
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        let location = [place.coordinates()];
+        let (forecast, archive) = tokio::join!(
+            self.fetch_previous_model_runs(
+                place.latitude,
+                place.longitude,
+                &params.start_date,
+                &params.end_date,
+                lead_days,
+                &options,
+            ),
+            self.fetch_historical_weather(
+                &location,
+                &params.start_date,
+                &params.end_date,
+                &options,
+            )
+        );
+
+        match forecast.and_then(|data| archive.map(|archive| (data, archive))) {
+            Ok((data, archive)) => {
+                let key = |variable: &str| format!("{}_previous_day{}", variable, lead_days);
+                let forecast = [
+                    self.daily_from_hourly(&data, &key("temperature_2m"), |s| s.max),
+                    self.daily_from_hourly(&data, &key("temperature_2m"), |s| s.min),
+                    self.daily_from_hourly(&data, &key("precipitation"), |s| s.sum),
+                    self.daily_from_hourly(&data, &key("wind_speed_10m"), |s| s.max),
+                ];
+                let formatted = self.format_verification(
+                    &format!(
+                        "Previous Model Runs Verification ({}-day lead time)",
+                        lead_days
+                    ),
+                    &place,
+                    &forecast,
+                    &archive,
+                    units,
+                );
+                tracing::info!("Successfully verified previous model runs");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving previous model runs: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "get_upper_air",
+        description = "Get an upper-air forecast on pressure levels for a specific location. Returns temperature, dew point, humidity, wind, and geopotential height per level and hour, plus derived freezing level, 850-500 hPa lapse rate, and K-index thunderstorm potential."
+    )]
+    async fn get_upper_air(
+        &self,
+        #[tool(aggr)] params: GetUpperAirParams,
+    ) -> Result<CallToolResult, McpError> {
+        let mut levels = params
+            .levels
+            .clone()
+            .unwrap_or_else(|| DEFAULT_UPPER_AIR_LEVELS.to_vec());
+        levels.sort_unstable_by(|a, b| b.cmp(a));
+        levels.dedup();
+        let hours = params.hours.unwrap_or(12).clamp(1, 384);
+
+        tracing::info!(
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            levels = ?levels,
+            hours = %hours,
+            timezone = %params.timezone.name(),
+            "Getting upper-air forecast"
+        );
+
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
+
+        if levels.is_empty() {
+            let err = "At least one pressure level must be given.".to_string();
+            tracing::error!("{}", err);
             return Ok(CallToolResult::error(vec![Content::text(err)]));
         }
+        if let Some(unknown) = levels.iter().find(|l| !PRESSURE_LEVELS.contains(l)) {
+            let available: Vec<String> = PRESSURE_LEVELS.iter().map(|l| l.to_string()).collect();
+            let err = format!(
+                "Invalid pressure level: {} hPa. Must be one of: {}.",
+                unknown,
+                available.join(", ")
+            );
+            tracing::error!("{}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let mut fetch_levels = levels.clone();
+        for level in INDEX_LEVELS {
+            if !fetch_levels.contains(&level) {
+                fetch_levels.push(level);
+            }
+        }
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
 
         match self
-            .fetch_weather_forecast(params.latitude, params.longitude, days)
+            .fetch_upper_air(
+                place.latitude,
+                place.longitude,
+                &fetch_levels,
+                hours,
+                &options,
+            )
             .await
         {
             Ok(data) => {
-                let formatted =
-                    self.format_weather_forecast(&data, params.latitude, params.longitude, days);
-                tracing::info!("Successfully retrieved weather forecast for {} days", days);
+                let formatted = self.format_upper_air(&data, &place, &levels, units);
+                tracing::info!(
+                    "Successfully retrieved upper-air forecast for {} hours",
+                    hours
+                );
                 Ok(CallToolResult::success(vec![Content::text(formatted)]))
             }
             Err(e) => {
-                let err_msg = format!("Error retrieving weather forecast: {}", e);
+                let err_msg = format!("Error retrieving upper-air forecast: {}", e);
                 tracing::error!("{}", err_msg);
                 Ok(CallToolResult::error(vec![Content::text(err_msg)]))
             }
@@ -657,58 +2633,102 @@ impl OpenMeteoServer {
     }
 
     #[tool(
-        name = "get_historical_weather",
-        description = "Get historical weather data for a specific location and date range. Returns daily weather statistics including temperature, precipitation, and other meteorological data for analysis."
+        name = "get_agriculture_weather",
+        description = "Get agricultural weather for a specific location, combining recent history from the archive with the forecast. Returns daily soil temperature and soil moisture at several depths, FAO-56 reference evapotranspiration (ET0), vapour pressure deficit, and a daily water balance (precipitation − ET0) with a running total."
     )]
-    async fn get_historical_weather(
+    async fn get_agriculture_weather(
         &self,
-        #[tool(aggr)] params: GetHistoricalWeatherParams,
+        #[tool(aggr)] params: GetAgricultureWeatherParams,
     ) -> Result<CallToolResult, McpError> {
+        let past_days = params.past_days.unwrap_or(7).min(92);
+        let forecast_days = params.forecast_days.unwrap_or(7).min(16);
+
         tracing::info!(
-            latitude = %params.latitude,
-            longitude = %params.longitude,
-            start_date = %params.start_date,
-            end_date = %params.end_date,
-            "Getting historical weather"
+            latitude = ?params.place.latitude,
+            longitude = ?params.place.longitude,
+            location = ?params.place.location,
+            past_days = %past_days,
+            forecast_days = %forecast_days,
+            timezone = %params.timezone.name(),
+            "Getting agriculture weather"
         );
 
-        if let Err(err) = self.validate_coordinates(params.latitude, params.longitude) {
-            tracing::error!("Invalid coordinates: {}", err);
-            return Ok(CallToolResult::error(vec![Content::text(err)]));
-        }
+        let options = match self.resolve_options(Some(&params.units), &params.timezone) {
+            Ok(options) => options,
+            Err(result) => return Ok(result),
+        };
+        let units = options.units;
 
-        if let Err(err) = self.validate_date(&params.start_date) {
-            tracing::error!("Invalid start date: {}", err);
+        if past_days == 0 && forecast_days == 0 {
+            let err = "At least one of 'past_days' and 'forecast_days' must be greater than 0."
+                .to_string();
+            tracing::error!("{}", err);
             return Ok(CallToolResult::error(vec![Content::text(err)]));
         }
 
-        if let Err(err) = self.validate_date(&params.end_date) {
-            tracing::error!("Invalid end date: {}", err);
-            return Ok(CallToolResult::error(vec![Content::text(err)]));
-        }
+        // The past days end with yesterday in the location's timezone, which is
+        // only known from a response: fetch a day more on each side of the UTC
+        // window and trim the archive to the local window afterwards.
+        let utc_today = chrono::Utc::now().date_naive();
+        let start_date = (utc_today - chrono::Duration::days(i64::from(past_days) + 1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let end_date = utc_today.format("%Y-%m-%d").to_string();
+
+        let archive_options = QueryOptions {
+            variables: self.agriculture_variables(&ARCHIVE_SOIL),
+            ..options.clone()
+        };
+        let forecast_options = QueryOptions {
+            variables: self.agriculture_variables(&FORECAST_SOIL),
+            ..options
+        };
+
+        let place = match self.resolve_location(&params.place).await {
+            Ok(place) => place,
+            Err(result) => return Ok(result),
+        };
+
+        let location = [place.coordinates()];
+        let (archive, forecast) = tokio::join!(
+            async {
+                if past_days == 0 {
+                    return Ok(None);
+                }
+                self.fetch_historical_weather(&location, &start_date, &end_date, &archive_options)
+                    .await
+                    .map(Some)
+            },
+            async {
+                if forecast_days == 0 {
+                    return Ok(None);
+                }
+                self.fetch_weather_forecast(&location, forecast_days, &forecast_options)
+                    .await
+                    .map(Some)
+            }
+        );
 
-        match self
-            .fetch_historical_weather(
-                params.latitude,
-                params.longitude,
-                &params.start_date,
-                &params.end_date,
-            )
-            .await
-        {
-            Ok(data) => {
-                let formatted = self.format_historical_weather(
-                    &data,
-                    params.latitude,
-                    params.longitude,
-                    &params.start_date,
-                    &params.end_date,
+        match archive.and_then(|archive| forecast.map(|forecast| (archive, forecast))) {
+            Ok((archive, forecast)) => {
+                let today = self.local_today(
+                    forecast
+                        .as_ref()
+                        .or(archive.as_ref())
+                        .unwrap_or(&Value::Null),
                 );
-                tracing::info!("Successfully retrieved historical weather data");
+                let formatted = self.format_agriculture_weather(
+                    archive.as_ref(),
+                    forecast.as_ref(),
+                    today - chrono::Duration::days(i64::from(past_days))..today,
+                    &place,
+                    units,
+                );
+                tracing::info!("Successfully retrieved agriculture weather");
                 Ok(CallToolResult::success(vec![Content::text(formatted)]))
             }
             Err(e) => {
-                let err_msg = format!("Error retrieving historical weather: {}", e);
+                let err_msg = format!("Error retrieving agriculture weather: {}", e);
                 tracing::error!("{}", err_msg);
                 Ok(CallToolResult::error(vec![Content::text(err_msg)]))
             }
@@ -728,12 +2748,45 @@ impl OpenMeteoServer {
         tracing::info!(
             query = %params.query,
             limit = %limit,
+            language = ?params.language,
+            country_code = ?params.country_code,
+            min_population = ?params.min_population,
             "Searching locations"
         );
 
-        match self.search_locations_helper(&params.query, limit).await {
+        let mut options = SearchOptions::default();
+        if let Some(language) = params.language.as_deref() {
+            match self.validate_language(language) {
+                Ok(language) => options.language = language,
+                Err(err) => {
+                    tracing::error!("Invalid language: {}", err);
+                    return Ok(CallToolResult::error(vec![Content::text(err)]));
+                }
+            }
+        }
+        if let Some(country_code) = params.country_code.as_deref() {
+            match self.validate_country_code(country_code) {
+                Ok(country_code) => options.country_code = Some(country_code),
+                Err(err) => {
+                    tracing::error!("Invalid country code: {}", err);
+                    return Ok(CallToolResult::error(vec![Content::text(err)]));
+                }
+            }
+        }
+
+        // The API has no population filter, so fetch the maximum and filter here.
+        let count = if params.min_population.is_some() {
+            100
+        } else {
+            limit
+        };
+
+        match self
+            .search_locations_helper(&params.query, count, &options)
+            .await
+        {
             Ok(data) => {
-                let formatted = self.format_locations(&data);
+                let formatted = self.format_locations(&data, params.min_population, limit as usize);
                 tracing::info!("Successfully searched locations");
                 Ok(CallToolResult::success(vec![Content::text(formatted)]))
             }
@@ -744,6 +2797,84 @@ impl OpenMeteoServer {
             }
         }
     }
+
+    #[tool(
+        name = "get_location",
+        description = "Get a single place by its geocoding ID, as shown in search_locations results. Use it to refer to an exact place without searching again for an ambiguous name."
+    )]
+    async fn get_location(
+        &self,
+        #[tool(aggr)] params: GetLocationParams,
+    ) -> Result<CallToolResult, McpError> {
+        tracing::info!(
+            id = %params.id,
+            language = ?params.language,
+            "Getting location"
+        );
+
+        let mut options = SearchOptions::default();
+        if let Some(language) = params.language.as_deref() {
+            match self.validate_language(language) {
+                Ok(language) => options.language = language,
+                Err(err) => {
+                    tracing::error!("Invalid language: {}", err);
+                    return Ok(CallToolResult::error(vec![Content::text(err)]));
+                }
+            }
+        }
+
+        match self.get_location_helper(params.id, &options).await {
+            Ok(data) if data.get("id").is_some() => {
+                let formatted = format!("🌍 Location:\n\n{}", self.format_location(&data));
+                tracing::info!("Successfully retrieved location");
+                Ok(CallToolResult::success(vec![Content::text(formatted)]))
+            }
+            Ok(_) => {
+                let err_msg = format!("No location found with ID {}.", params.id);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+            Err(e) => {
+                let err_msg = format!("Error retrieving location: {}", e);
+                tracing::error!("{}", err_msg);
+                Ok(CallToolResult::error(vec![Content::text(err_msg)]))
+            }
+        }
+    }
+
+    #[tool(
+        name = "reverse_geocode",
+        description = "Find the nearest named places to a pair of coordinates using a locally loaded GeoNames cities file. Works without network access; requires the server to be started with GEONAMES_CITIES_FILE set."
+    )]
+    async fn reverse_geocode(
+        &self,
+        #[tool(aggr)] params: ReverseGeocodeParams,
+    ) -> Result<CallToolResult, McpError> {
+        let limit = params.limit.unwrap_or(5).clamp(1, 20);
+
+        tracing::info!(
+            latitude = %params.latitude,
+            longitude = %params.longitude,
+            limit = %limit,
+            "Reverse geocoding"
+        );
+
+        if let Err(err) = self.validate_coordinates(params.latitude, params.longitude) {
+            tracing::error!("Invalid coordinates: {}", err);
+            return Ok(CallToolResult::error(vec![Content::text(err)]));
+        }
+
+        let Some(cities) = &self.cities else {
+            let err_msg = "Reverse geocoding is unavailable: start the server with --geonames-file (or GEONAMES_CITIES_FILE) pointing to a GeoNames cities file such as cities500.txt.".to_string();
+            tracing::error!("{}", err_msg);
+            return Ok(CallToolResult::error(vec![Content::text(err_msg)]));
+        };
+
+        let places = cities.nearest(params.latitude, params.longitude, limit as usize);
+        let formatted = self.format_reverse_geocode(params.latitude, params.longitude, &places);
+        tracing::info!("Successfully reverse geocoded coordinates");
+        Ok(CallToolResult::success(vec![Content::text(formatted)]))
+    }
 }
 
 #[tool(tool_box)]
@@ -760,46 +2891,902 @@ impl ServerHandler for OpenMeteoServer {
                 name: env!("CARGO_PKG_NAME").to_string(),
                 version: env!("CARGO_PKG_VERSION").to_string(),
             },
-            instructions: Some(
+            instructions: Some(format!(
                 "This server provides tools to interact with the OpenMeteo Weather API for weather data and forecasts.\n\
                 Available tools:\n\
                 - 'get_current_weather': Get current weather conditions for a specific location. \
-                Requires 'latitude' and 'longitude' parameters.\n\
+                Requires 'latitude' and 'longitude' parameters or a 'locations' list. Optional 'models' list.\n\
                 - 'get_weather_forecast': Get weather forecast for a specific location. \
-                Requires 'latitude' and 'longitude' parameters. Optional 'days' parameter (1-16, defaults to 7).\n\
+                Requires 'latitude' and 'longitude' parameters or a 'locations' list. Optional 'days' parameter (1-16, defaults to {}) and 'models' list.\n\
+                - 'get_hourly_forecast': Get an hour-by-hour weather forecast for a specific location. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'hours' parameter (1-384, defaults to 24) \
+                and optional 'start_time' parameter (YYYY-MM-DDTHH:MM in the selected timezone, defaults to the current hour).\n\
                 - 'get_historical_weather': Get historical weather data for a specific location and date range. \
-                Requires 'latitude', 'longitude', 'start_date', and 'end_date' parameters (dates in YYYY-MM-DD format).\n\
+                Requires 'latitude' and 'longitude' (or a 'locations' list), 'start_date', and 'end_date' parameters (dates in YYYY-MM-DD format). Optional 'models' list.\n\
                 - 'search_locations': Search for locations by name to get their coordinates. \
                 Requires 'query' parameter in format 'city, country' (country is optional, e.g., 'Paris, France' or 'Tokyo'). \
-                Optional 'limit' parameter (defaults to 10, max 100).\n\n\
+                Optional 'limit' parameter (defaults to 10, max 100), 'language' (ISO 639-1, defaults to 'en'), \
+                'country_code' (ISO 3166-1 alpha-2), and 'min_population'.\n\
+                - 'get_location': Get a single place by the geocoding ID shown in search results. \
+                Requires 'id' parameter. Optional 'language' (ISO 639-1, defaults to 'en').\n\
+                - 'reverse_geocode': Find the nearest named places to coordinates from a local GeoNames cities file. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'limit' (1-20, defaults to 5).\n\
+                - 'get_air_quality': Get current air quality (AQI, pollutants, and pollen) for a specific location. \
+                Requires 'latitude' and 'longitude' parameters.\n\
+                - 'get_marine_forecast': Get waves, swell, and sea surface temperature for a coastal or offshore location. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'resolution' parameter ('daily' or 'hourly', defaults to 'daily') \
+                and optional 'days' parameter (1-16, defaults to 7 for daily and 1 for hourly). Inland locations return an error.\n\
+                - 'get_river_discharge': Get past and forecast daily river discharge from the GloFAS flood model. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'past_days' (0-92, defaults to 7) and 'forecast_days' \
+                (1-210, defaults to 92) parameters, or 'start_date' and 'end_date' (YYYY-MM-DD) together.\n\
+                - 'get_ensemble_forecast': Get per-day min/median/max across ensemble members and exceedance probabilities. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'model' ('icon_eps', 'gfs_ensemble', 'ecmwf_ifs_ensemble', \
+                'gem_ensemble'), 'variable' ('precipitation', 'temperature_max', 'temperature_min', 'wind_speed_max'), \
+                'threshold', and 'days' (1-35, defaults to 7) parameters.\n\
+                - 'get_climate_projection': Get CMIP6 climate projections with annual, decadal, and seasonal temperature and precipitation. \
+                Requires 'latitude', 'longitude', 'start_year', and 'end_year' (1950-2050) parameters. Optional 'models' list.\n\
+                - 'get_seasonal_forecast': Get weekly or monthly temperature and precipitation anomalies up to 9 months ahead. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'months' (1-9, defaults to 6) and 'period' \
+                ('monthly' or 'weekly', defaults to 'monthly') parameters.\n\
+                - 'get_elevation': Get the terrain elevation for one or more points. \
+                Requires a 'locations' list of 1-100 objects with 'latitude' and 'longitude'.\n\
+                - 'get_solar_radiation': Get daily solar irradiation (kWh/m²) and estimated PV yield. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'tilt' (0-90, defaults to 30), 'azimuth' \
+                (0 = south, defaults to 0), 'system_size_kwp', 'efficiency' (0-1, defaults to 0.8), and 'days' (1-16, defaults to 7) parameters.\n\
+                - 'get_historical_forecast': Verify archived forecasts against observations (bias and MAE). \
+                Requires 'latitude', 'longitude', 'start_date', and 'end_date' parameters (dates in YYYY-MM-DD format).\n\
+                - 'get_previous_model_runs': Verify what was forecast a number of days in advance against observations. \
+                Requires 'latitude', 'longitude', 'start_date', and 'end_date' parameters. Optional 'lead_days' (1-7, defaults to 3).\n\
+                - 'get_upper_air': Get pressure-level temperature, humidity, wind, and heights with freezing level, lapse rate, and K-index. \
+                Requires 'latitude' and 'longitude' parameters. Optional 'levels' list in hPa (defaults to 1000, 925, 850, 700, 500, 300) \
+                and 'hours' (1-384, defaults to 12) parameters.\n\
+                - 'get_agriculture_weather': Get soil temperature and moisture, ET0, vapour pressure deficit, and a running water balance \
+                from the archive and the forecast. Requires 'latitude' and 'longitude' parameters. Optional 'past_days' (0-92, defaults to 7) \
+                and 'forecast_days' (0-16, defaults to 7) parameters.\n\n\
                 Coordinates must be valid: latitude between -90 and 90, longitude between -180 and 180.\n\
-                All weather data is provided by OpenMeteo (https://open-meteo.com/) and is free to use."
-                    .to_string(),
-            ),
+                Every weather tool also accepts a 'location' place name (e.g. 'Paris, France') instead of 'latitude' and 'longitude'; \
+                the top geocoding match is used and ambiguous names are reported with their candidates.\n\
+                'locations' is a list of up to 100 objects with 'latitude' and 'longitude', reported one section per point.\n\
+                All weather tools accept 'units' ('metric' or 'imperial', defaults to 'metric') and the overrides \
+                'temperature_unit' ('celsius', 'fahrenheit'), 'wind_speed_unit' ('kmh', 'ms', 'mph', 'kn'), and 'precipitation_unit' ('mm', 'inch').\n\
+                'get_current_weather', 'get_weather_forecast', and 'get_historical_weather' accept a 'variables' list of extra \
+                Open-Meteo variables (e.g. 'dew_point_2m', 'uv_index_clear_sky_max'), shown in tables below the report.\n\
+                All weather tools accept 'timezone': 'auto' (default, the location's local time) or an IANA timezone name such as 'Europe/Paris'.\n\
+                The 'models' list selects specific weather models (e.g. 'ecmwf_ifs025', 'gfs_seamless', 'icon_seamless'; \
+                'era5', 'era5_land', 'cerra' for historical data). Several models are compared side-by-side.\n\
+                All weather data is provided by OpenMeteo (https://open-meteo.com/) and is free to use.",
+                self.config.forecast_days
+            )),
         }
     }
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    let config = Config::parse();
+
     tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::DEBUG.into()),
-        )
+        .with_env_filter(tracing_subscriber::EnvFilter::try_new(&config.log_level)?)
         .with_writer(std::io::stderr)
         .init();
 
     tracing::info!("Starting OpenMeteo MCP Server...");
 
+    let transport = config.transport;
+    let bind = std::net::SocketAddr::new(config.host, config.port);
+    let health_bind = std::net::SocketAddr::new(config.host, config.health_port);
+
     // Create an instance of our OpenMeteo server
-    let server = OpenMeteoServer::new().expect("Error initializing OpenMeteo server");
+    let server = OpenMeteoServer::new(config).expect("Error initializing OpenMeteo server");
 
-    tracing::info!("Using stdio transport");
-    let service = server.serve(stdio()).await.inspect_err(|e| {
-        tracing::error!("serving error: {:?}", e);
-    })?;
+    match transport {
+        Transport::Stdio => {
+            tracing::info!("Using stdio transport");
+            let service = server.serve(stdio()).await.inspect_err(|e| {
+                tracing::error!("serving error: {:?}", e);
+            })?;
 
-    service.waiting().await?;
+            service.waiting().await?;
+        }
+        Transport::Sse => {
+            tracing::info!("Using SSE transport");
+            http::serve(bind, health_bind, move || server.clone()).await?;
+        }
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn server() -> OpenMeteoServer {
+        OpenMeteoServer::new(Config::parse_from(["mcp-server-openmeteo"])).unwrap()
+    }
+
+    fn point(latitude: f64, longitude: f64) -> Place {
+        Place {
+            latitude,
+            longitude,
+            label: None,
+        }
+    }
+
+    #[test]
+    fn hourly_forecast_groups_hours_by_day_up_to_the_limit() {
+        let data = json!({
+            "hourly": {
+                "time": ["2024-06-01T22:00", "2024-06-01T23:00", "2024-06-02T00:00", "2024-06-02T01:00"],
+                "temperature_2m": [18.4, 17.9, 17.2, 16.8],
+                "precipitation_probability": [10, 20, 30, 40],
+                "precipitation": [0.0, 0.2, 0.4, 0.6],
+                "weather_code": [0, 3, 61, 61],
+                "wind_speed_10m": [5.0, 6.0, 7.0, 8.0],
+                "wind_direction_10m": [180, 190, 200, 210],
+                "wind_gusts_10m": [9.0, 10.0, 11.0, 12.0],
+                "cloud_cover": [0, 100, 100, 100],
+                "visibility": [24000, 20000, 9000, 8000],
+            },
+            "hourly_units": {"temperature_2m": "°C", "precipitation": "mm", "wind_speed_10m": "km/h", "visibility": "m"},
+        });
+        let report =
+            server().format_hourly_forecast(&data, &point(52.52, 13.41), 3, Units::default());
+
+        assert!(report.starts_with("🌍 3-Hour Weather Forecast\nLocation: 52.52°, 13.41°\n"));
+        assert!(report.contains("\n📅 2024-06-01\n🕐 22:00 | 🌡️ 18.4°C"));
+        assert!(report.contains("\n📅 2024-06-02\n🕐 00:00 | 🌡️ 17.2°C"));
+        assert!(report.contains("☔ 30% (0.4mm)"));
+        assert!(!report.contains("01:00"));
+    }
+
+    #[test]
+    fn air_quality_rates_indices_and_pollutants_and_notes_missing_pollen() {
+        let data = json!({
+            "current": {
+                "time": "2024-06-01T12:00",
+                "european_aqi": 45.0,
+                "us_aqi": 120.0,
+                "pm2_5": 22.5,
+                "carbon_monoxide": 180.0,
+            },
+            "current_units": {"pm2_5": "μg/m³", "carbon_monoxide": "μg/m³"},
+        });
+        let report = server().format_air_quality(&data, &point(40.71, -74.01));
+
+        assert!(report.starts_with(
+            "🌍 Current Air Quality\nLocation: 40.71°, -74.01°\nTime: 2024-06-01T12:00\n"
+        ));
+        assert!(report.contains("🇪🇺 European AQI: 45 (Moderate)"));
+        assert!(report.contains("🇺🇸 US AQI: 120 (Unhealthy for sensitive groups)"));
+        assert!(report.contains("• PM2.5: 22.5μg/m³ (Moderate)\n"));
+        assert!(report.contains("• Carbon monoxide (CO): 180.0μg/m³\n"));
+        assert!(!report.contains("PM10"));
+        assert!(report.contains("Pollen data is not available for this region"));
+    }
+
+    #[test]
+    fn marine_daily_summary_averages_hourly_sea_temperature_per_day() {
+        let data = json!({
+            "hourly": {
+                "time": ["2024-06-01T00:00", "2024-06-01T12:00", "2024-06-02T00:00"],
+                "sea_surface_temperature": [18.0, 19.0, null],
+            },
+            "hourly_units": {"sea_surface_temperature": "°C"},
+            "daily": {
+                "time": ["2024-06-01", "2024-06-02"],
+                "wave_height_max": [1.24, null],
+                "wave_direction_dominant": [270, 280],
+                "wave_period_max": [8.0, 9.0],
+            },
+            "daily_units": {"wave_height_max": "m", "wave_direction_dominant": "°", "wave_period_max": "s"},
+        });
+        let report =
+            server().format_marine_forecast(&data, &point(43.3, -1.98), false, 2, Units::default());
+
+        assert!(report.starts_with("🌊 2-Day Marine Forecast\nLocation: 43.30°, -1.98°\n"));
+        assert!(report
+            .contains("📅 2024-06-01\n🌊 Max wave height: 1.2m from 270°, period up to 8.0s\n"));
+        assert!(report.contains("🌡️ Sea surface: 18.5°C\n"));
+        assert!(report.contains("📅 2024-06-02\n🌊 Max wave height: n/a from 280°"));
+        assert!(report.contains("🏄 Max swell: n/a from n/a, period up to n/a"));
+        assert!(report.ends_with("🌡️ Sea surface: n/a\n\n"));
+    }
+
+    #[test]
+    fn river_discharge_flags_days_well_above_the_median() {
+        let data = json!({
+            "daily": {
+                "time": ["2000-01-01", "2999-01-01", "2999-01-02"],
+                "river_discharge": [120.0, 300.0, null],
+                "river_discharge_mean": [110.0, 280.0, 90.0],
+                "river_discharge_median": [100.0, 100.0, 100.0],
+            },
+            "daily_units": {"river_discharge": "m³/s"},
+        });
+        let report = server().format_river_discharge(&data, &point(50.94, 6.96));
+
+        assert!(report.starts_with("🏞️ River Discharge\nLocation: 50.94°, 6.96°\nUnit: m³/s\n"));
+        assert!(
+            report.contains("\n⏪ Past\n📅 2000-01-01: 120.0 (mean 110.0, median 100.0, max n/a")
+        );
+        assert!(report.contains("\n⏩ Forecast\n📅 2999-01-01: 300.0"));
+        assert!(report.contains("⚠️ 3.0× median\n"));
+        // Without a discharge value the ensemble mean stands in.
+        assert!(report.contains("📅 2999-01-02: 90.0 (mean 90.0"));
+        assert!(report.contains("⚠️ 1 day(s) exceed"));
+        assert!(report.ends_with("ensemble median: 2999-01-01\n"));
+    }
+
+    #[test]
+    fn ensemble_forecast_summarises_daily_member_totals() {
+        let data = json!({
+            "hourly": {
+                "time": ["2024-06-01T00:00", "2024-06-01T01:00", "2024-06-02T00:00"],
+                "precipitation": [1.0, 2.0, 0.0],
+                "precipitation_member01": [0.0, 0.5, 0.0],
+                "precipitation_member02": [4.0, 4.0, null],
+                "temperature_2m_member01": [99.0, 99.0, 99.0],
+            },
+            "hourly_units": {"precipitation": "mm"},
+        });
+        let report = server().format_ensemble_forecast(
+            &data,
+            &point(48.14, 11.58),
+            "ECMWF IFS",
+            EnsembleVariable::Precipitation,
+            Some(1.0),
+            Units::default(),
+        );
+
+        assert!(report.starts_with(
+            "🎲 Ensemble Forecast (ECMWF IFS, 3 members)\nLocation: 48.14°, 11.58°\n"
+        ));
+        assert!(report.contains("Threshold: > 1mm\n"));
+        assert!(report
+            .contains("📅 2024-06-01: min 0.5mm | median 3.0mm | max 8.0mm | P(> 1mm): 67%\n"));
+        // A member without values for the day is left out of that day.
+        assert!(
+            report.contains("📅 2024-06-02: min 0.0mm | median 0.0mm | max 0.0mm | P(> 1mm): 0%\n")
+        );
+    }
+
+    #[test]
+    fn climate_projection_does_not_borrow_another_models_series() {
+        let data = json!({
+            "daily": {
+                "time": ["2030-01-01", "2030-01-02"],
+                "temperature_2m_mean": [10.0, 10.0],
+                "temperature_2m_mean_model_a": [1.0, 3.0],
+                "precipitation_sum_model_a": [0.5, 1.5],
+            },
+            "daily_units": {},
+        });
+        let models = ["model_a".to_string(), "model_b".to_string()];
+        let report = server().format_climate_projection(
+            &data,
+            &point(0.0, 0.0),
+            &models,
+            2030,
+            2030,
+            Units::default(),
+        );
+
+        assert!(report.contains("2030: mean 2.0°C (avg high n/a, avg low n/a), precipitation 2mm"));
+        assert!(report.contains("🧪 Model: model_b\nNo data returned for this model."));
+        assert!(!report.contains("10.0"));
+    }
+
+    #[test]
+    fn climate_projection_reads_unsuffixed_series_of_a_single_model() {
+        let data = json!({
+            "daily": {
+                "time": ["2030-07-01"],
+                "temperature_2m_mean": [20.0],
+            },
+        });
+        let models = ["model_a".to_string()];
+        let report = server().format_climate_projection(
+            &data,
+            &point(0.0, 0.0),
+            &models,
+            2030,
+            2030,
+            Units::default(),
+        );
+
+        assert!(report.contains("2030: mean 20.0°C"));
+        assert!(report.contains("JJA: mean 20.0°C, precipitation n/a"));
+    }
+
+    #[test]
+    fn validate_timezone_accepts_auto_and_iana_names() {
+        let server = server();
+        assert!(server.validate_timezone("auto").is_ok());
+        assert!(server.validate_timezone("Europe/Paris").is_ok());
+        assert!(server.validate_timezone("GMT").is_ok());
+        assert!(server
+            .validate_timezone("Mars/Olympus")
+            .unwrap_err()
+            .contains("'Mars/Olympus'"));
+    }
+
+    #[test]
+    fn resolve_options_defaults_to_auto_timezone_and_metric_units() {
+        let server = server();
+        let options = server
+            .resolve_options(None, &TimezoneParams::default())
+            .unwrap();
+        assert_eq!(options.timezone, "auto");
+        assert_eq!(options.units, Units::default());
+
+        let params = TimezoneParams {
+            timezone: Some("Paris".to_string()),
+        };
+        assert!(server.resolve_options(None, &params).is_err());
+        let units = UnitParams {
+            units: Some("kelvin".to_string()),
+            ..Default::default()
+        };
+        assert!(server
+            .resolve_options(Some(&units), &TimezoneParams::default())
+            .is_err());
+    }
+
+    #[test]
+    fn format_timezone_shows_signed_offsets_with_minutes() {
+        let server = server();
+        let header = |timezone: &str, abbreviation: &str, offset: i64| {
+            server.format_timezone(&json!({
+                "timezone": timezone,
+                "timezone_abbreviation": abbreviation,
+                "utc_offset_seconds": offset,
+            }))
+        };
+        assert_eq!(
+            header("America/St_Johns", "NDT", -9000),
+            "\nTimezone: America/St_Johns (NDT, UTC-02:30)"
+        );
+        assert_eq!(
+            header("Asia/Kolkata", "IST", 19800),
+            "\nTimezone: Asia/Kolkata (IST, UTC+05:30)"
+        );
+        assert_eq!(header("GMT", "GMT", 0), "\nTimezone: GMT (UTC+00:00)");
+        assert_eq!(server.format_timezone(&json!({})), "");
+    }
+
+    #[test]
+    fn variable_tables_cap_hourly_rows() {
+        let times: Vec<String> = (0..24 * 365)
+            .map(|hour| format!("2024-01-01T{:02}:00", hour % 24))
+            .collect();
+        let data = json!({
+            "hourly": { "time": times, "cape": vec![1.0; 24 * 365] },
+            "daily": { "time": ["2024-01-01", "2024-01-02", "2024-01-03"], "rain_sum": [0.0, 1.0, 2.0] },
+        });
+        let variables = [
+            Variable {
+                block: Block::Hourly,
+                name: "cape",
+            },
+            Variable {
+                block: Block::Daily,
+                name: "rain_sum",
+            },
+        ];
+
+        let report =
+            server().with_variable_tables(String::new(), &data, &variables, &[], usize::MAX);
+        assert_eq!(report.matches("2024-01-01T").count(), MAX_HOURLY_TABLE_ROWS);
+        assert!(report.contains("… showing the first 48 of 8760 rows"));
+        assert!(report.contains("2024-01-03 | 2.0"));
+
+        let report = server().with_variable_tables(String::new(), &data, &variables, &[], 1);
+        assert_eq!(report.matches("2024-01-01T").count(), 24);
+        assert!(!report.contains("2024-01-02 |"));
+    }
+
+    #[test]
+    fn daily_normals_average_each_calendar_day_across_years() {
+        let climatology = json!({
+            "daily": {
+                "time": ["1991-01-01", "1991-01-02", "1992-01-01"],
+                "temperature_2m_max": [4.0, 6.0, 8.0],
+                "temperature_2m_min": [0.0, 2.0, 2.0],
+                "precipitation_sum": [1.0, null, 3.0],
+            },
+        });
+        let normals = server().daily_normals(&climatology);
+
+        assert_eq!(normals["01-01"], json!([3.5, 2.0]));
+        assert_eq!(normals["01-02"], json!([4.0, null]));
+        assert_eq!(normals.as_object().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn elevations_are_listed_per_point_and_shown_in_report_headers() {
+        let locations = [
+            Coordinates {
+                latitude: 46.5586,
+                longitude: 7.8964,
+            },
+            Coordinates {
+                latitude: 0.0,
+                longitude: 0.0,
+            },
+        ];
+        let report = server().format_elevations(&json!({"elevation": [4158.4]}), &locations);
+        assert_eq!(
+            report,
+            "⛰️ Elevation (90m digital elevation model)\n\n📍 46.5586°, 7.8964°: 4158m\n📍 0.0000°, 0.0000°: Unknown\n"
+        );
+
+        let report = server().format_current_weather(
+            &json!({"elevation": 38.0}),
+            &point(52.52, 13.41),
+            Units::default(),
+        );
+        assert!(report
+            .starts_with("🌍 Current Weather\nLocation: 52.52°, 13.41° (grid elevation: 38m)\n"));
+    }
+
+    #[test]
+    fn solar_radiation_sums_daily_energy_and_pv_yield() {
+        let data = json!({
+            "hourly": {
+                "time": ["2024-06-01T11:00", "2024-06-01T12:00", "2024-06-02T12:00"],
+                "shortwave_radiation": [600.0, 800.0, 400.0],
+                "direct_radiation": [400.0, 600.0, 100.0],
+                "diffuse_radiation": [200.0, 200.0, 300.0],
+                "direct_normal_irradiance": [700.0, 900.0, 150.0],
+                "global_tilted_irradiance": [700.0, 900.0, null],
+            },
+        });
+        let report =
+            server().format_solar_radiation(&data, &point(37.39, -5.98), 30.0, 0.0, Some(5.0), 0.8);
+
+        assert!(report.starts_with(
+            "🔆 Solar Radiation Forecast\nLocation: 37.39°, -5.98°\nPanel: tilt 30°, azimuth 0°\nPV system: 5.0 kWp at 80% efficiency\n"
+        ));
+        assert!(report.contains(
+            "📅 2024-06-01\n☀️ Global horizontal: 1.40 kWh/m² (direct 1.00, diffuse 0.40)\n🎯 Direct normal: 1.60 kWh/m²\n📐 Tilted plane: 1.60 kWh/m² (peak 900 W/m²)\n⚡ Estimated PV yield: 6.4 kWh\n"
+        ));
+        assert!(report.contains(
+            "📐 Tilted plane: 0.00 kWh/m² (peak 0 W/m²)\n⚡ Estimated PV yield: 0.0 kWh\n"
+        ));
+        assert!(report.contains("📊 Total tilted-plane irradiation: 1.60 kWh/m² over 2 days\n"));
+        assert!(report.ends_with("⚡ Total estimated PV yield: 6.4 kWh\n"));
+    }
+
+    #[test]
+    fn verification_reports_bias_and_mae_over_overlapping_days() {
+        let archive = json!({
+            "daily": {
+                "time": ["2024-05-01", "2024-05-02", "2024-05-03"],
+                "temperature_2m_max": [20.0, 22.0, null],
+                "precipitation_sum": [0.0, 3.0, 1.0],
+            },
+            "daily_units": {"temperature_2m_max": "°C", "precipitation_sum": "mm"},
+        });
+        let series = |values: &[(&str, f64)]| -> DailySeries {
+            values.iter().map(|(d, v)| (d.to_string(), *v)).collect()
+        };
+        let forecast = [
+            series(&[
+                ("2024-05-01", 21.0),
+                ("2024-05-02", 19.0),
+                ("2024-05-03", 25.0),
+            ]),
+            DailySeries::new(),
+            series(&[("2024-05-02", 4.0)]),
+            DailySeries::new(),
+        ];
+        let report = server().format_verification(
+            "Check",
+            &point(51.51, -0.13),
+            &forecast,
+            &archive,
+            Units::default(),
+        );
+
+        assert!(report.starts_with("🎯 Check\nLocation: 51.51°, -0.13°\n"));
+        assert!(report.contains("• Max temperature: bias -1.00°C, MAE 2.00°C (2 days)\n"));
+        assert!(report.contains("• Min temperature: no overlapping data\n"));
+        assert!(report.contains("• Precipitation: bias +1.00mm, MAE 1.00mm (1 days)\n"));
+        assert!(report.contains("2024-05-01: Max temperature 21.0/20.0°C\n"));
+        assert!(
+            report.contains("2024-05-02: Max temperature 19.0/22.0°C, Precipitation 4.0/3.0mm\n")
+        );
+        assert!(!report.contains("2024-05-03"));
+    }
+
+    #[test]
+    fn daily_from_hourly_keeps_only_complete_days() {
+        let mut times = Vec::new();
+        let mut values = Vec::new();
+        for hour in 0..24 {
+            times.push(format!("2024-05-01T{:02}:00", hour));
+            values.push(json!(hour));
+        }
+        times.push("2024-05-02T00:00".to_string());
+        values.push(json!(50));
+        let data = json!({"hourly": {"time": times, "temperature_2m": values}});
+
+        let daily = server().daily_from_hourly(&data, "temperature_2m", |s| s.max);
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily["2024-05-01"], 23.0);
+    }
+
+    #[test]
+    fn model_comparison_lists_each_model_side_by_side() {
+        let data = json!({
+            "daily": {
+                "time": ["2024-06-01", "2024-06-02"],
+                "temperature_2m_max_icon_seamless": [24.0, 26.0],
+                "temperature_2m_min_icon_seamless": [12.0, 14.0],
+                "precipitation_sum_icon_seamless": [0.0, 2.5],
+                "weather_code_icon_seamless": [0, 61],
+                "temperature_2m_max_gfs_seamless": [23.0, null],
+            },
+            "daily_units": {"temperature_2m_max_icon_seamless": "°C"},
+        });
+        let models = ["icon_seamless".to_string(), "gfs_seamless".to_string()];
+        let report = server().format_model_comparison(
+            &data,
+            "Forecast",
+            &point(48.85, 2.35),
+            &models,
+            1,
+            Units::default(),
+        );
+
+        assert!(report.starts_with("🌍 Forecast — Model Comparison\nLocation: 48.85°, 2.35°\n"));
+        assert!(report
+            .contains("🧮 DWD ICON: avg high 25.0°C, avg low 13.0°C, total precipitation 2.5mm\n"));
+        assert!(
+            report.contains("🧮 NCEP GFS: avg high 23.0°C, avg low n/a, total precipitation n/a\n")
+        );
+        assert!(report.contains("\n📅 2024-06-01\n"));
+        assert!(!report.contains("2024-06-02"));
+        assert!(
+            report.contains("🧮 DWD ICON: 🌡️ 24.0°C / 12.0°C | ☔ 0.0mm | 💨 n/a | ☀️ Clear sky\n")
+        );
+    }
+
+    #[test]
+    fn models_are_validated_against_the_catalog_and_named_in_headers() {
+        let server = server();
+        assert!(server
+            .validate_models(&["icon_seamless".to_string()], &FORECAST_MODELS)
+            .is_ok());
+        let err = server
+            .validate_models(&["era6".to_string()], &ARCHIVE_MODELS)
+            .unwrap_err();
+        assert!(
+            err.starts_with("Invalid model: 'era6'. Must be one of: "),
+            "{}",
+            err
+        );
+
+        let report = server.with_model_header(
+            "🌍 Title\nLocation: 0.00°, 0.00°\n".to_string(),
+            &["gfs_seamless".to_string()],
+        );
+        assert_eq!(
+            report,
+            "🌍 Title\nModel: NCEP GFS\nLocation: 0.00°, 0.00°\n"
+        );
+    }
+
+    #[test]
+    fn agriculture_water_balance_runs_across_past_and_forecast_days() {
+        let archive = json!({
+            "utc_offset_seconds": 3600,
+            "daily": {
+                "time": ["2024-06-01", "2024-06-02", "2024-06-03", "2024-06-04"],
+                "precipitation_sum": [9.0, 4.0, 0.0, 9.0],
+                "et0_fao_evapotranspiration": [1.0, 1.0, 2.0, 1.0],
+            },
+        });
+        let forecast = json!({
+            "utc_offset_seconds": 3600,
+            "daily": {
+                "time": ["2024-06-04", "2024-06-05"],
+                "precipitation_sum": [1.0, null],
+                "et0_fao_evapotranspiration": [3.0, 2.0],
+            },
+        });
+        let server = server();
+        let today = NaiveDate::from_ymd_opt(2024, 6, 4).unwrap();
+        let report = server.format_agriculture_weather(
+            Some(&archive),
+            Some(&forecast),
+            today - chrono::Duration::days(2)..today,
+            &point(52.52, 13.41),
+            Units::default(),
+        );
+
+        // The extra archive days around the local window are trimmed.
+        assert!(!report.contains("2024-06-01"));
+        assert!(report.contains("+3.0mm (running total +3.0mm)"));
+        assert!(report.contains("-2.0mm (running total +1.0mm)"));
+        assert!(report.contains("-2.0mm (running total -1.0mm)"));
+        assert!(report.contains("water balance n/a"));
+        assert!(report.contains("Water balance over 3 days"));
+    }
+
+    #[test]
+    fn batch_queries_list_points_and_split_the_response_per_point() {
+        let locations = [
+            Coordinates {
+                latitude: 52.52,
+                longitude: 13.41,
+            },
+            Coordinates {
+                latitude: -33.87,
+                longitude: 151.21,
+            },
+        ];
+        assert_eq!(
+            Coordinates::to_query(&locations),
+            "latitude=52.52,-33.87&longitude=13.41,151.21"
+        );
+
+        let server = server();
+        let batch = json!([{"latitude": 52.5}, {"latitude": -33.875}]);
+        let parts = server.split_locations(&batch);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[1]["latitude"], -33.875);
+
+        let single = json!({"latitude": 52.5});
+        assert_eq!(server.split_locations(&single), vec![&single]);
+    }
+
+    fn springfields() -> Value {
+        json!({
+            "results": [
+                {"name": "Springfield", "latitude": 37.2, "longitude": -93.3, "country": "United States", "country_code": "US", "admin1": "Missouri", "population": 169176},
+                {"name": "Springfield", "latitude": 42.1, "longitude": -72.6, "country": "United States", "country_code": "US", "admin1": "Massachusetts", "population": 155929},
+                {"name": "Springfield", "latitude": 39.8, "longitude": -89.6, "country": "United States", "country_code": "US", "admin1": "Illinois", "population": 114394},
+                {"name": "Springfield", "latitude": -27.7, "longitude": 153.0, "country": "Australia", "country_code": "AU", "admin1": "Queensland", "population": 20000},
+            ],
+        })
+    }
+
+    #[test]
+    fn best_match_filters_by_name_prefix_and_abbreviation_qualifiers() {
+        let server = server();
+        for query in [
+            "Springfield, Illinois",
+            "Springfield, ill",
+            "Springfield, IL",
+            "Springfield, IL, US",
+        ] {
+            let place = server.best_match(query, &springfields()).unwrap();
+            assert_eq!(
+                (place.latitude, place.longitude),
+                (39.8, -89.6),
+                "{}",
+                query
+            );
+        }
+        let place = server
+            .best_match("Springfield, AU", &springfields())
+            .unwrap();
+        assert_eq!(
+            place.label.as_deref(),
+            Some("Springfield, Queensland, Australia")
+        );
+
+        // 'ON' is Ontario, not a prefix of any field here.
+        let err = server
+            .best_match("Springfield, ON", &springfields())
+            .unwrap_err();
+        assert!(
+            err.starts_with("No location found for 'Springfield, ON'."),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn best_match_reports_names_of_comparable_population_as_ambiguous() {
+        let server = server();
+        let err = server
+            .best_match("Springfield", &springfields())
+            .unwrap_err();
+        assert!(err.contains("is ambiguous"), "{}", err);
+        assert!(
+            err.contains("Springfield, Illinois, United States"),
+            "{}",
+            err
+        );
+        // A quarter of the top population is the cut-off.
+        assert!(!err.contains("Australia"), "{}", err);
+
+        let place = server
+            .best_match("Springfield, US, Missouri", &springfields())
+            .unwrap();
+        assert_eq!((place.latitude, place.longitude), (37.2, -93.3));
+
+        let data = json!({
+            "results": [
+                {"name": "Paris", "latitude": 48.9, "longitude": 2.4, "country": "France", "population": 2138551},
+                {"name": "Paris", "latitude": 33.7, "longitude": -95.6, "country": "United States", "population": 24782},
+            ],
+        });
+        let place = server.best_match("Paris", &data).unwrap();
+        assert_eq!((place.latitude, place.longitude), (48.9, 2.4));
+    }
+
+    #[test]
+    fn report_headers_lead_with_the_geocoded_place_name() {
+        let data = json!({"timezone": "Europe/Paris", "utc_offset_seconds": 7200});
+        let place = Place {
+            label: Some("Paris, Île-de-France, France".to_string()),
+            ..point(48.853, 2.349)
+        };
+        let report = server().format_air_quality(&data, &place);
+        assert!(
+            report.contains(
+                "Location: Paris, Île-de-France, France — 48.85°, 2.35°\nTimezone: Europe/Paris"
+            ),
+            "{}",
+            report
+        );
+
+        let report = server().format_air_quality(&data, &point(48.853, 2.349));
+        assert!(
+            report.contains("Location: 48.85°, 2.35°\nTimezone"),
+            "{}",
+            report
+        );
+    }
+
+    #[test]
+    fn location_search_filters_by_population_and_describes_each_place() {
+        let options = SearchOptions {
+            language: "de".to_string(),
+            country_code: Some("FR".to_string()),
+        };
+        assert_eq!(options.to_query(), "&language=de&countryCode=FR");
+        assert_eq!(SearchOptions::default().to_query(), "&language=en");
+
+        let server = server();
+        assert_eq!(server.validate_language("DE").unwrap(), "de");
+        assert!(server.validate_language("deu").is_err());
+        assert_eq!(server.validate_country_code("fr").unwrap(), "FR");
+
+        let data = json!({
+            "results": [
+                {"name": "Hamlet", "country": "France", "population": 120},
+                {"name": "Unknown size", "country": "France"},
+                {
+                    "name": "Paris", "country": "France", "country_code": "FR",
+                    "admin1": "Île-de-France", "admin2": "Paris",
+                    "latitude": 48.85341, "longitude": 2.3488, "elevation": 42.0,
+                    "feature_code": "PPLC", "timezone": "Europe/Paris", "population": 2138551,
+                    "postcodes": ["75001", "75002", "75003", "75004", "75005", "75006", "75007"]
+                },
+                {"name": "Lyon", "country": "France", "population": 522969},
+            ],
+        });
+        let report = server.format_locations(&data, Some(1000), 1);
+        assert!(!report.contains("Hamlet"), "{}", report);
+        assert!(!report.contains("Unknown size"), "{}", report);
+        assert!(!report.contains("Lyon"), "{}", report);
+        assert!(
+            report.contains("1. 📍 Paris, Île-de-France, France (FR)\n🗺️ Subdivisions: Paris"),
+            "{}",
+            report
+        );
+        assert!(report.contains("⛰️ Elevation: 42m"), "{}", report);
+        assert!(
+            report.contains("🏷️ Feature: PPLC (capital of a political entity)"),
+            "{}",
+            report
+        );
+        assert!(
+            report.contains("📮 Postcodes: 75001, 75002, 75003, 75004, 75005 (+2 more)"),
+            "{}",
+            report
+        );
+
+        let report = server.format_locations(&data, None, 10);
+        assert!(report.contains("2. 📍 Unknown size, France"), "{}", report);
+        assert!(report.contains("4. 📍 Lyon, France"), "{}", report);
+    }
+
+    #[test]
+    fn locations_show_their_geocoding_id_below_the_name() {
+        let paris = json!({
+            "id": 2988507, "name": "Paris", "country": "France", "country_code": "FR",
+            "admin1": "Île-de-France", "latitude": 48.85341, "longitude": 2.3488,
+            "timezone": "Europe/Paris", "population": 2138551,
+        });
+        let server = server();
+        let location = server.format_location(&paris);
+        assert!(
+            location.starts_with(
+                "📍 Paris, Île-de-France, France (FR)\n🆔 ID: 2988507\n📍 Coordinates: 48.8534°, 2.3488°"
+            ),
+            "{}",
+            location
+        );
+        assert!(
+            location.ends_with("🕐 Timezone: Europe/Paris\n👥 Population: 2138551"),
+            "{}",
+            location
+        );
+
+        let report = server.format_locations(&json!({"results": [paris]}), None, 10);
+        assert!(report.contains("1. 📍 Paris, Île-de-France"), "{}", report);
+        assert!(report.contains("🆔 ID: 2988507"), "{}", report);
+    }
+
+    fn server_with_key(base_url: &str) -> OpenMeteoServer {
+        OpenMeteoServer::new(Config::parse_from([
+            "mcp-server-openmeteo",
+            "--api-key",
+            "secret-key",
+            "--base-url",
+            base_url,
+            "--cache-ttl",
+            "60",
+        ]))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn request_errors_do_not_show_the_api_key() {
+        let server = server_with_key("http://127.0.0.1:1");
+        let url = format!(
+            "{}/v1/forecast?latitude=1&longitude=2",
+            server.base_url(Api::Forecast)
+        );
+        let err = server.get_json(&url, "Weather API").await.unwrap_err();
+        let shown = format!("{:#}", err);
+        assert!(shown.contains("127.0.0.1:1"), "{}", shown);
+        assert!(
+            !shown.contains("apikey") && !shown.contains("secret-key"),
+            "{}",
+            shown
+        );
+    }
+
+    #[tokio::test]
+    async fn api_key_is_sent_but_left_out_of_cache_keys_and_decode_errors() {
+        let router = axum::Router::new()
+            .route(
+                "/v1/forecast",
+                axum::routing::get(|uri: axum::http::Uri| async move {
+                    axum::Json(json!({ "query": uri.query() }))
+                }),
+            )
+            .route("/v1/text", axum::routing::get(|| async { "not json" }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let server = server_with_key(&base);
+        let url = format!(
+            "{}/v1/forecast?latitude=1&longitude=2",
+            server.base_url(Api::Forecast)
+        );
+        let data = server.get_json(&url, "Weather API").await.unwrap();
+        assert_eq!(data["query"], "latitude=1&longitude=2&apikey=secret-key");
+        assert_eq!(server.cache.get(&url), Some(data));
+
+        let url = format!("{}/v1/text", server.base_url(Api::Forecast));
+        let shown = format!(
+            "{:#}",
+            server.get_json(&url, "Weather API").await.unwrap_err()
+        );
+        assert!(shown.contains("/v1/text"), "{}", shown);
+        assert!(
+            !shown.contains("apikey") && !shown.contains("secret-key"),
+            "{}",
+            shown
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Waves, swell, and sea surface temperature from the marine API.
+
+use crate::{config::Api, units::Units, OpenMeteoServer, Place, QueryOptions};
+use serde_json::Value;
+
+impl OpenMeteoServer {
+    pub async fn fetch_marine_forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        hourly: bool,
+        days: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let variables = if hourly {
+            "hourly=wave_height,wave_direction,wave_period,swell_wave_height,swell_wave_direction,swell_wave_period,wind_wave_height,wind_wave_direction,wind_wave_period,sea_surface_temperature"
+        } else {
+            "daily=wave_height_max,wave_direction_dominant,wave_period_max,swell_wave_height_max,swell_wave_direction_dominant,swell_wave_period_max,wind_wave_height_max&hourly=sea_surface_temperature"
+        };
+        let url = format!(
+            "{}/v1/marine?latitude={}&longitude={}&{}&forecast_days={}{}",
+            self.base_url(Api::Marine),
+            latitude,
+            longitude,
+            variables,
+            days,
+            options.to_marine_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Marine API").await
+    }
+
+    pub fn format_marine_forecast(
+        &self,
+        data: &Value,
+        place: &Place,
+        hourly: bool,
+        days: u32,
+        units: Units,
+    ) -> String {
+        let hourly_block = data.get("hourly").unwrap_or(&Value::Null);
+        let hourly_units = data.get("hourly_units").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let hourly_times = hourly_block
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let sea_temp = hourly_block
+            .get("sea_surface_temperature")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let sea_temp_unit = hourly_units
+            .get("sea_surface_temperature")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.temperature.label());
+
+        let fmt_value = |value: Option<f64>, precision: usize, unit: &str| match value {
+            Some(v) => format!("{:.*}{}", precision, v, unit),
+            None => "n/a".to_string(),
+        };
+
+        if hourly {
+            let series = |key: &str| {
+                hourly_block
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .unwrap_or(&empty_vec)
+            };
+            let unit = |key: &str| hourly_units.get(key).and_then(|v| v.as_str()).unwrap_or("");
+            let wave_height = series("wave_height");
+            let wave_direction = series("wave_direction");
+            let wave_period = series("wave_period");
+            let swell_height = series("swell_wave_height");
+            let swell_direction = series("swell_wave_direction");
+            let swell_period = series("swell_wave_period");
+            let wind_wave_height = series("wind_wave_height");
+
+            let mut forecast = format!(
+                "🌊 {}-Day Hourly Marine Forecast\nLocation: {}{}\n",
+                days,
+                place,
+                self.format_timezone(data)
+            );
+
+            let mut current_day = "";
+            for (i, time) in hourly_times.iter().enumerate() {
+                let time = time.as_str().unwrap_or("Unknown");
+                let (day, hour) = time.split_once('T').unwrap_or((time, ""));
+                if day != current_day {
+                    forecast.push_str(&format!("\n📅 {}\n", day));
+                    current_day = day;
+                }
+
+                let at = |values: &Vec<Value>| values.get(i).and_then(|v| v.as_f64());
+                forecast.push_str(&format!(
+                    "🕐 {} | 🌊 Waves {} from {} every {} | 🏄 Swell {} from {} every {} | 💨 Wind waves {} | 🌡️ Sea {}\n",
+                    hour,
+                    fmt_value(at(wave_height), 1, unit("wave_height")),
+                    fmt_value(at(wave_direction), 0, unit("wave_direction")),
+                    fmt_value(at(wave_period), 1, unit("wave_period")),
+                    fmt_value(at(swell_height), 1, unit("swell_wave_height")),
+                    fmt_value(at(swell_direction), 0, unit("swell_wave_direction")),
+                    fmt_value(at(swell_period), 1, unit("swell_wave_period")),
+                    fmt_value(at(wind_wave_height), 1, unit("wind_wave_height")),
+                    fmt_value(at(sea_temp), 1, sea_temp_unit),
+                ));
+            }
+
+            return forecast;
+        }
+
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+        let series = |key: &str| {
+            daily
+                .get(key)
+                .and_then(|v| v.as_array())
+                .unwrap_or(&empty_vec)
+        };
+        let unit = |key: &str| daily_units.get(key).and_then(|v| v.as_str()).unwrap_or("");
+        let dates = series("time");
+        let wave_height = series("wave_height_max");
+        let wave_direction = series("wave_direction_dominant");
+        let wave_period = series("wave_period_max");
+        let swell_height = series("swell_wave_height_max");
+        let swell_direction = series("swell_wave_direction_dominant");
+        let swell_period = series("swell_wave_period_max");
+        let wind_wave_height = series("wind_wave_height_max");
+
+        let mut forecast = format!(
+            "🌊 {}-Day Marine Forecast\nLocation: {}{}\n\n",
+            days,
+            place,
+            self.format_timezone(data)
+        );
+
+        for (i, date) in dates.iter().enumerate() {
+            let date = date.as_str().unwrap_or("Unknown");
+            let at = |values: &Vec<Value>| values.get(i).and_then(|v| v.as_f64());
+
+            // Daily sea surface temperature is the mean of that day's hourly values
+            let day_temps: Vec<f64> = hourly_times
+                .iter()
+                .zip(sea_temp)
+                .filter(|(time, _)| time.as_str().is_some_and(|t| t.starts_with(date)))
+                .filter_map(|(_, temp)| temp.as_f64())
+                .collect();
+            let mean_sea_temp = if day_temps.is_empty() {
+                None
+            } else {
+                Some(day_temps.iter().sum::<f64>() / day_temps.len() as f64)
+            };
+
+            forecast.push_str(&format!(
+                "📅 {}\n🌊 Max wave height: {} from {}, period up to {}\n🏄 Max swell: {} from {}, period up to {}\n💨 Max wind waves: {}\n🌡️ Sea surface: {}\n\n",
+                date,
+                fmt_value(at(wave_height), 1, unit("wave_height_max")),
+                fmt_value(at(wave_direction), 0, unit("wave_direction_dominant")),
+                fmt_value(at(wave_period), 1, unit("wave_period_max")),
+                fmt_value(at(swell_height), 1, unit("swell_wave_height_max")),
+                fmt_value(at(swell_direction), 0, unit("swell_wave_direction_dominant")),
+                fmt_value(at(swell_period), 1, unit("swell_wave_period_max")),
+                fmt_value(at(wind_wave_height), 1, unit("wind_wave_height_max")),
+                fmt_value(mean_sea_temp, 1, sea_temp_unit),
+            ));
+        }
+
+        forecast
+    }
+}
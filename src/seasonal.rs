@@ -0,0 +1,241 @@
+//! Seasonal forecasts from the seasonal API, with anomalies against archive normals.
+
+use crate::{
+    config::Api, stats::Summary, units::Units, Coordinates, OpenMeteoServer, Place, QueryOptions,
+};
+use chrono::{Datelike, NaiveDate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Reference period for seasonal anomalies (WMO climate normal).
+const CLIMATE_NORMAL_START: &str = "1991-01-01";
+const CLIMATE_NORMAL_END: &str = "2020-12-31";
+
+/// Longest horizon served by the seasonal forecast API.
+pub const MAX_SEASONAL_FORECAST_DAYS: u32 = 274;
+
+impl OpenMeteoServer {
+    pub async fn fetch_seasonal_forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        forecast_days: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/seasonal?latitude={}&longitude={}&daily=temperature_2m_max,temperature_2m_min,precipitation_sum&forecast_days={}{}",
+            self.base_url(Api::Seasonal),
+            latitude,
+            longitude,
+            forecast_days,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Seasonal API").await
+    }
+
+    /// Daily climate normals (see `daily_normals`) near a point. Coordinates are
+    /// rounded to 0.1° (about 11 km), so the 30 years of archive data are
+    /// fetched once per rounded coordinate, units and timezone.
+    pub async fn climate_normals(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let round = |degrees: f64| (degrees * 10.0).round() / 10.0;
+        let location = [Coordinates {
+            latitude: round(latitude),
+            longitude: round(longitude),
+        }];
+        let key = format!(
+            "{:.1},{:.1}{}",
+            location[0].latitude,
+            location[0].longitude,
+            options.to_query()
+        );
+        if let Some(normals) = self.normals.get(&key) {
+            tracing::debug!("Climate normals cache hit: {}", key);
+            return Ok(normals);
+        }
+
+        let climatology = self
+            .fetch_historical_weather(&location, CLIMATE_NORMAL_START, CLIMATE_NORMAL_END, options)
+            .await?;
+        let normals = self.daily_normals(&climatology);
+        self.normals.insert(key, normals.clone());
+        Ok(normals)
+    }
+
+    /// Mean temperature and precipitation per calendar day of an archive
+    /// response, as an object mapping "MM-DD" to `[temperature, precipitation]`.
+    pub fn daily_normals(&self, climatology: &Value) -> Value {
+        let empty_vec = vec![];
+        let daily = climatology.get("daily").unwrap_or(&Value::Null);
+        let series = |key: &str| {
+            daily
+                .get(key)
+                .and_then(|v| v.as_array())
+                .unwrap_or(&empty_vec)
+        };
+        let normal_max = series("temperature_2m_max");
+        let normal_min = series("temperature_2m_min");
+        let normal_precip = series("precipitation_sum");
+
+        let mut days: BTreeMap<String, (Summary, Summary)> = BTreeMap::new();
+        for (i, date) in series("time").iter().enumerate() {
+            let Some(date) = date.as_str().and_then(|d| self.validate_date(d).ok()) else {
+                continue;
+            };
+            let day = days.entry(date.format("%m-%d").to_string()).or_default();
+            if let (Some(max), Some(min)) = (
+                normal_max.get(i).and_then(|v| v.as_f64()),
+                normal_min.get(i).and_then(|v| v.as_f64()),
+            ) {
+                day.0.push((max + min) / 2.0);
+            }
+            if let Some(precip) = normal_precip.get(i).and_then(|v| v.as_f64()) {
+                day.1.push(precip);
+            }
+        }
+
+        days.into_iter()
+            .map(|(day, (temp, precip))| (day, serde_json::json!([temp.mean(), precip.mean()])))
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+
+    pub fn format_seasonal_forecast(
+        &self,
+        data: &Value,
+        normals: &Value,
+        place: &Place,
+        weekly: bool,
+        units: Units,
+    ) -> String {
+        let empty_vec = vec![];
+
+        let daily = data.get("daily").unwrap_or(&Value::Null);
+        let daily_units = data.get("daily_units").unwrap_or(&Value::Null);
+        let temp_unit = daily_units
+            .get("temperature_2m_max")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.temperature.label());
+        let precip_unit = daily_units
+            .get("precipitation_sum")
+            .and_then(|v| v.as_str())
+            .unwrap_or(units.precipitation.label());
+
+        // Ensemble members are keyed "<variable>" and "<variable>_memberNN"
+        let members = |variable: &str| -> Vec<&Vec<Value>> {
+            let prefix = format!("{}_member", variable);
+            daily
+                .as_object()
+                .map(|block| {
+                    block
+                        .iter()
+                        .filter(|(key, _)| *key == variable || key.starts_with(&prefix))
+                        .filter_map(|(_, values)| values.as_array())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let max_members = members("temperature_2m_max");
+        let min_members = members("temperature_2m_min");
+        let precip_members = members("precipitation_sum");
+
+        let dates: Vec<Option<NaiveDate>> = daily
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec)
+            .iter()
+            .map(|v| v.as_str().and_then(|d| self.validate_date(d).ok()))
+            .collect();
+        let first_date = dates.iter().flatten().next().copied();
+
+        // Per period: ensemble temperature, ensemble precipitation, normal
+        // temperature, normal precipitation.
+        let mut periods: BTreeMap<NaiveDate, [Summary; 4]> = BTreeMap::new();
+        for (i, date) in dates.iter().enumerate() {
+            let (Some(date), Some(first)) = (date, first_date) else {
+                continue;
+            };
+            let period_start = if weekly {
+                first + chrono::Duration::days((*date - first).num_days() / 7 * 7)
+            } else {
+                date.with_day(1).unwrap_or(*date)
+            };
+            let period = periods.entry(period_start).or_default();
+
+            for (max, min) in max_members.iter().zip(&min_members) {
+                if let (Some(max), Some(min)) = (
+                    max.get(i).and_then(|v| v.as_f64()),
+                    min.get(i).and_then(|v| v.as_f64()),
+                ) {
+                    period[0].push((max + min) / 2.0);
+                }
+            }
+            // Precipitation is summed per member, so store the member mean per day
+            let daily_precip: Summary = precip_members
+                .iter()
+                .filter_map(|values| values.get(i).and_then(|v| v.as_f64()))
+                .collect();
+            if let Some(precip) = daily_precip.mean() {
+                period[1].push(precip);
+            }
+
+            let normal = normals.get(date.format("%m-%d").to_string());
+            let normal_at = |i: usize| normal.and_then(|n| n.get(i)).and_then(|v| v.as_f64());
+            if let Some(t) = normal_at(0) {
+                period[2].push(t);
+            }
+            if let Some(p) = normal_at(1) {
+                period[3].push(p);
+            }
+        }
+
+        let mut forecast = format!(
+            "🌍 Seasonal Forecast ({} summary, {} ensemble members)\nLocation: {}{}\nAnomalies relative to {} to {}\n\n",
+            if weekly { "weekly" } else { "monthly" },
+            max_members.len(),
+            place,
+            self.format_timezone(data),
+            CLIMATE_NORMAL_START,
+            CLIMATE_NORMAL_END
+        );
+
+        for (start, [temp, precip, normal_temp, normal_precip]) in &periods {
+            let label = if weekly {
+                format!("Week of {}", start.format("%Y-%m-%d"))
+            } else {
+                start.format("%Y-%m").to_string()
+            };
+            let Some(mean_temp) = temp.mean() else {
+                forecast.push_str(&format!("📅 {}: no forecast data\n", label));
+                continue;
+            };
+
+            let temp_anomaly = match normal_temp.mean() {
+                Some(normal) => format!(" (anomaly {:+.1}{})", mean_temp - normal, temp_unit),
+                None => String::new(),
+            };
+            let precip_anomaly = if normal_precip.sum > 0.0 {
+                format!(
+                    " ({:.0}% of normal, {:+.0}{})",
+                    precip.sum / normal_precip.sum * 100.0,
+                    precip.sum - normal_precip.sum,
+                    precip_unit
+                )
+            } else {
+                String::new()
+            };
+
+            forecast.push_str(&format!(
+                "📅 {}\n🌡️ Mean temperature: {:.1}{}{}\n☔ Precipitation: {:.0}{}{}\n\n",
+                label, mean_temp, temp_unit, temp_anomaly, precip.sum, precip_unit, precip_anomaly
+            ));
+        }
+
+        forecast
+    }
+}
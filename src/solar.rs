@@ -0,0 +1,136 @@
+//! Solar radiation and PV yield estimates from the forecast API.
+
+use crate::{config::Api, stats::Summary, OpenMeteoServer, Place, QueryOptions};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+impl OpenMeteoServer {
+    pub async fn fetch_solar_radiation(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        tilt: f64,
+        azimuth: f64,
+        days: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&hourly=shortwave_radiation,direct_radiation,diffuse_radiation,direct_normal_irradiance,global_tilted_irradiance&tilt={}&azimuth={}&forecast_days={}{}",
+            self.base_url(Api::Forecast),
+            latitude,
+            longitude,
+            tilt,
+            azimuth,
+            days,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo API").await
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn format_solar_radiation(
+        &self,
+        data: &Value,
+        place: &Place,
+        tilt: f64,
+        azimuth: f64,
+        system_size_kwp: Option<f64>,
+        efficiency: f64,
+    ) -> String {
+        let hourly = data.get("hourly").unwrap_or(&Value::Null);
+
+        let empty_vec = vec![];
+        let times = hourly
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let variables = [
+            "shortwave_radiation",
+            "direct_radiation",
+            "diffuse_radiation",
+            "direct_normal_irradiance",
+            "global_tilted_irradiance",
+        ];
+        let series: Vec<&Vec<Value>> = variables
+            .iter()
+            .map(|key| {
+                hourly
+                    .get(*key)
+                    .and_then(|v| v.as_array())
+                    .unwrap_or(&empty_vec)
+            })
+            .collect();
+
+        // Hourly values are mean W/m² over the preceding hour, so their daily
+        // sum in Wh/m² divided by 1000 is the daily energy in kWh/m².
+        let mut days: BTreeMap<&str, [Summary; 5]> = BTreeMap::new();
+        for (i, time) in times.iter().enumerate() {
+            let day = time
+                .as_str()
+                .and_then(|t| t.split_once('T'))
+                .map(|(day, _)| day)
+                .unwrap_or("Unknown");
+            let totals = days.entry(day).or_default();
+            for (total, values) in totals.iter_mut().zip(&series) {
+                if let Some(v) = values.get(i).and_then(|v| v.as_f64()) {
+                    total.push(v);
+                }
+            }
+        }
+
+        let mut report = format!(
+            "🔆 Solar Radiation Forecast\nLocation: {}{}\nPanel: tilt {:.0}°, azimuth {:.0}°\n",
+            place,
+            self.format_timezone(data),
+            tilt,
+            azimuth
+        );
+        if let Some(size) = system_size_kwp {
+            report.push_str(&format!(
+                "PV system: {:.1} kWp at {:.0}% efficiency\n",
+                size,
+                efficiency * 100.0
+            ));
+        }
+        report.push('\n');
+
+        let mut total_yield = 0.0;
+        let mut total_tilted = 0.0;
+        for (day, [global, direct, diffuse, normal, tilted]) in &days {
+            let tilted_kwh = tilted.sum / 1000.0;
+            total_tilted += tilted_kwh;
+            report.push_str(&format!(
+                "📅 {}\n☀️ Global horizontal: {:.2} kWh/m² (direct {:.2}, diffuse {:.2})\n🎯 Direct normal: {:.2} kWh/m²\n📐 Tilted plane: {:.2} kWh/m² (peak {:.0} W/m²)\n",
+                day,
+                global.sum / 1000.0,
+                direct.sum / 1000.0,
+                diffuse.sum / 1000.0,
+                normal.sum / 1000.0,
+                tilted_kwh,
+                if tilted.is_empty() { 0.0 } else { tilted.max }
+            ));
+            if let Some(size) = system_size_kwp {
+                // kWp is rated at 1 kW/m² irradiance, so yield scales with tilted-plane energy
+                let energy = tilted_kwh * size * efficiency;
+                total_yield += energy;
+                report.push_str(&format!("⚡ Estimated PV yield: {:.1} kWh\n", energy));
+            }
+            report.push('\n');
+        }
+
+        report.push_str(&format!(
+            "📊 Total tilted-plane irradiation: {:.2} kWh/m² over {} days\n",
+            total_tilted,
+            days.len()
+        ));
+        if system_size_kwp.is_some() {
+            report.push_str(&format!(
+                "⚡ Total estimated PV yield: {:.1} kWh\n",
+                total_yield
+            ));
+        }
+
+        report
+    }
+}
@@ -0,0 +1,125 @@
+//! Summary statistics shared by the formatters.
+
+/// Running count, sum, minimum and maximum of a series of values.
+#[derive(Debug, Clone, Copy)]
+pub struct Summary {
+    pub count: usize,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl Default for Summary {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            sum: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl Summary {
+    pub fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.sum / self.count as f64)
+        }
+    }
+}
+
+impl FromIterator<f64> for Summary {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut summary = Self::default();
+        for value in iter {
+            summary.push(value);
+        }
+        summary
+    }
+}
+
+/// Median of the values, sorting them in place.
+pub fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let n = values.len();
+    Some(if n.is_multiple_of(2) {
+        (values[n / 2 - 1] + values[n / 2]) / 2.0
+    } else {
+        values[n / 2]
+    })
+}
+
+/// Meteorological seasons (Northern Hemisphere naming).
+pub const SEASONS: [&str; 4] = ["DJF", "MAM", "JJA", "SON"];
+
+/// Index into [`SEASONS`] for a calendar month (1-12).
+pub fn season_index(month: u32) -> usize {
+    match month {
+        12 | 1 | 2 => 0,
+        3..=5 => 1,
+        6..=8 => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_tracks_count_sum_and_extremes() {
+        let summary: Summary = [3.0, -1.5, 4.5].into_iter().collect();
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.sum, 6.0);
+        assert_eq!(summary.min, -1.5);
+        assert_eq!(summary.max, 4.5);
+        assert_eq!(summary.mean(), Some(2.0));
+    }
+
+    #[test]
+    fn empty_summary_has_no_mean() {
+        let summary = Summary::default();
+        assert!(summary.is_empty());
+        assert_eq!(summary.mean(), None);
+    }
+
+    #[test]
+    fn median_of_odd_and_even_lengths() {
+        assert_eq!(median(&mut [5.0, 1.0, 3.0]), Some(3.0));
+        assert_eq!(median(&mut [4.0, 1.0, 3.0, 2.0]), Some(2.5));
+        assert_eq!(median(&mut [7.0]), Some(7.0));
+        assert_eq!(median(&mut []), None);
+    }
+
+    #[test]
+    fn median_sorts_in_place() {
+        let mut values = [2.0, -1.0, 1.0];
+        median(&mut values);
+        assert_eq!(values, [-1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn seasons_group_months() {
+        let seasons: Vec<&str> = (1..=12).map(|m| SEASONS[season_index(m)]).collect();
+        assert_eq!(
+            seasons,
+            ["DJF", "DJF", "MAM", "MAM", "MAM", "JJA", "JJA", "JJA", "SON", "SON", "SON", "DJF"]
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Unit selection shared by the weather tools.
+
+use rmcp::schemars;
+
+/// Unit parameters accepted by every weather tool. Individual units override
+/// the preset.
+#[derive(Debug, Default, serde::Deserialize, schemars::JsonSchema)]
+pub struct UnitParams {
+    #[schemars(
+        description = "Unit preset: 'metric' (°C, km/h, mm) or 'imperial' (°F, mph, inch). Defaults to the server's configured preset, normally 'metric'."
+    )]
+    pub units: Option<String>,
+    #[schemars(description = "Temperature unit: 'celsius' or 'fahrenheit'. Overrides the preset.")]
+    pub temperature_unit: Option<String>,
+    #[schemars(
+        description = "Wind speed unit: 'kmh', 'ms', 'mph', or 'kn'. Overrides the preset."
+    )]
+    pub wind_speed_unit: Option<String>,
+    #[schemars(description = "Precipitation unit: 'mm' or 'inch'. Overrides the preset.")]
+    pub precipitation_unit: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TemperatureUnit {
+    #[default]
+    Celsius,
+    Fahrenheit,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WindSpeedUnit {
+    #[default]
+    Kmh,
+    Ms,
+    Mph,
+    Knots,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PrecipitationUnit {
+    #[default]
+    Millimeter,
+    Inch,
+}
+
+/// Resolved units for one request.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Units {
+    pub temperature: TemperatureUnit,
+    pub wind_speed: WindSpeedUnit,
+    pub precipitation: PrecipitationUnit,
+}
+
+impl TemperatureUnit {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "celsius" => Ok(Self::Celsius),
+            "fahrenheit" => Ok(Self::Fahrenheit),
+            _ => Err(format!(
+                "Invalid temperature unit: '{}'. Must be 'celsius' or 'fahrenheit'.",
+                name
+            )),
+        }
+    }
+
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::Celsius => "celsius",
+            Self::Fahrenheit => "fahrenheit",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Celsius => "°C",
+            Self::Fahrenheit => "°F",
+        }
+    }
+
+    /// Converts a value in this unit to degrees Celsius.
+    pub fn to_celsius(self, value: f64) -> f64 {
+        match self {
+            Self::Celsius => value,
+            Self::Fahrenheit => (value - 32.0) * 5.0 / 9.0,
+        }
+    }
+}
+
+impl WindSpeedUnit {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "kmh" => Ok(Self::Kmh),
+            "ms" => Ok(Self::Ms),
+            "mph" => Ok(Self::Mph),
+            "kn" => Ok(Self::Knots),
+            _ => Err(format!(
+                "Invalid wind speed unit: '{}'. Must be 'kmh', 'ms', 'mph', or 'kn'.",
+                name
+            )),
+        }
+    }
+
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::Kmh => "kmh",
+            Self::Ms => "ms",
+            Self::Mph => "mph",
+            Self::Knots => "kn",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Kmh => "km/h",
+            Self::Ms => "m/s",
+            Self::Mph => "mp/h",
+            Self::Knots => "kn",
+        }
+    }
+}
+
+impl PrecipitationUnit {
+    fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "mm" => Ok(Self::Millimeter),
+            "inch" => Ok(Self::Inch),
+            _ => Err(format!(
+                "Invalid precipitation unit: '{}'. Must be 'mm' or 'inch'.",
+                name
+            )),
+        }
+    }
+
+    fn api_name(self) -> &'static str {
+        match self {
+            Self::Millimeter => "mm",
+            Self::Inch => "inch",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Millimeter => "mm",
+            Self::Inch => "inch",
+        }
+    }
+}
+
+impl Units {
+    pub const IMPERIAL: Units = Units {
+        temperature: TemperatureUnit::Fahrenheit,
+        wind_speed: WindSpeedUnit::Mph,
+        precipitation: PrecipitationUnit::Inch,
+    };
+
+    pub fn preset(name: &str) -> Result<Self, String> {
+        match name {
+            "metric" => Ok(Self::default()),
+            "imperial" => Ok(Self::IMPERIAL),
+            _ => Err(format!(
+                "Invalid units preset: '{}'. Must be 'metric' or 'imperial'.",
+                name
+            )),
+        }
+    }
+
+    /// Query parameters for the units that differ from the API defaults.
+    pub fn to_query(self) -> String {
+        let mut query = String::new();
+        if self.temperature != TemperatureUnit::default() {
+            query.push_str(&format!(
+                "&temperature_unit={}",
+                self.temperature.api_name()
+            ));
+        }
+        if self.wind_speed != WindSpeedUnit::default() {
+            query.push_str(&format!("&wind_speed_unit={}", self.wind_speed.api_name()));
+        }
+        if self.precipitation != PrecipitationUnit::default() {
+            query.push_str(&format!(
+                "&precipitation_unit={}",
+                self.precipitation.api_name()
+            ));
+        }
+        query
+    }
+
+    /// The marine API has no precipitation unit; wave heights follow the
+    /// precipitation unit instead (inch selects feet).
+    pub fn to_marine_query(self) -> String {
+        let mut query = String::new();
+        if self.temperature != TemperatureUnit::default() {
+            query.push_str(&format!(
+                "&temperature_unit={}",
+                self.temperature.api_name()
+            ));
+        }
+        if self.precipitation == PrecipitationUnit::Inch {
+            query.push_str("&length_unit=imperial");
+        }
+        query
+    }
+
+    /// Fallback unit label for an API variable, used when the response
+    /// carries no unit block.
+    pub fn label_for(self, variable: &str) -> &'static str {
+        if variable.contains("temperature") {
+            self.temperature.label()
+        } else if variable.starts_with("precipitation") || variable.starts_with("rain") {
+            self.precipitation.label()
+        } else if variable.starts_with("wind_speed") || variable.starts_with("wind_gusts") {
+            self.wind_speed.label()
+        } else {
+            ""
+        }
+    }
+}
+
+impl UnitParams {
+    /// Resolves the preset and overrides, starting from `default`.
+    pub fn resolve(&self, default: Units) -> Result<Units, String> {
+        let mut units = match self.units.as_deref() {
+            Some(preset) => Units::preset(preset)?,
+            None => default,
+        };
+        if let Some(name) = self.temperature_unit.as_deref() {
+            units.temperature = TemperatureUnit::parse(name)?;
+        }
+        if let Some(name) = self.wind_speed_unit.as_deref() {
+            units.wind_speed = WindSpeedUnit::parse(name)?;
+        }
+        if let Some(name) = self.precipitation_unit.as_deref() {
+            units.precipitation = PrecipitationUnit::parse(name)?;
+        }
+        Ok(units)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(units: Option<&str>, temperature: Option<&str>, wind: Option<&str>) -> UnitParams {
+        UnitParams {
+            units: units.map(str::to_string),
+            temperature_unit: temperature.map(str::to_string),
+            wind_speed_unit: wind.map(str::to_string),
+            precipitation_unit: None,
+        }
+    }
+
+    #[test]
+    fn resolve_falls_back_to_the_default_preset() {
+        assert_eq!(
+            params(None, None, None).resolve(Units::IMPERIAL),
+            Ok(Units::IMPERIAL)
+        );
+        assert_eq!(
+            params(Some("metric"), None, None).resolve(Units::IMPERIAL),
+            Ok(Units::default())
+        );
+    }
+
+    #[test]
+    fn resolve_overrides_beat_the_preset() {
+        let units = params(Some("imperial"), Some("celsius"), Some("kn"))
+            .resolve(Units::default())
+            .unwrap();
+        assert_eq!(units.temperature, TemperatureUnit::Celsius);
+        assert_eq!(units.wind_speed, WindSpeedUnit::Knots);
+        assert_eq!(units.precipitation, PrecipitationUnit::Inch);
+    }
+
+    #[test]
+    fn resolve_rejects_unknown_names() {
+        assert!(params(Some("nautical"), None, None)
+            .resolve(Units::default())
+            .unwrap_err()
+            .contains("'nautical'"));
+        assert!(params(None, Some("kelvin"), None)
+            .resolve(Units::default())
+            .unwrap_err()
+            .contains("'kelvin'"));
+    }
+
+    #[test]
+    fn to_query_omits_api_defaults() {
+        assert_eq!(Units::default().to_query(), "");
+        assert_eq!(
+            Units::IMPERIAL.to_query(),
+            "&temperature_unit=fahrenheit&wind_speed_unit=mph&precipitation_unit=inch"
+        );
+        let units = Units {
+            wind_speed: WindSpeedUnit::Ms,
+            ..Units::default()
+        };
+        assert_eq!(units.to_query(), "&wind_speed_unit=ms");
+    }
+
+    #[test]
+    fn to_marine_query_maps_inch_to_imperial_lengths() {
+        assert_eq!(Units::default().to_marine_query(), "");
+        assert_eq!(
+            Units::IMPERIAL.to_marine_query(),
+            "&temperature_unit=fahrenheit&length_unit=imperial"
+        );
+        let units = Units {
+            wind_speed: WindSpeedUnit::Knots,
+            ..Units::default()
+        };
+        assert_eq!(units.to_marine_query(), "");
+    }
+}
@@ -0,0 +1,300 @@
+//! Upper-air forecasts on pressure levels and the indicators derived from
+//! their profiles.
+
+use crate::{config::Api, units::Units, OpenMeteoServer, Place, QueryOptions};
+use serde_json::Value;
+
+/// Pressure levels offered by the forecast API, in hPa.
+pub const PRESSURE_LEVELS: [u32; 19] = [
+    1000, 975, 950, 925, 900, 850, 800, 700, 600, 500, 400, 300, 250, 200, 150, 100, 70, 50, 30,
+];
+
+/// One pressure level of a sounding, temperatures in °C and height in m.
+#[derive(Debug, Clone, Copy)]
+pub struct Level {
+    pub pressure: u32,
+    pub temperature: f64,
+    pub dew_point: f64,
+    pub height: f64,
+}
+
+/// Height of the lowest 0°C crossing, interpolated linearly in height
+/// between levels. `levels` must be ordered from the bottom up.
+pub fn freezing_level(levels: &[Level]) -> Option<f64> {
+    levels.windows(2).find_map(|pair| {
+        let (lower, upper) = (pair[0], pair[1]);
+        if lower.temperature >= 0.0 && upper.temperature < 0.0 {
+            let fraction = lower.temperature / (lower.temperature - upper.temperature);
+            Some(lower.height + fraction * (upper.height - lower.height))
+        } else {
+            None
+        }
+    })
+}
+
+/// Environmental lapse rate between two levels in °C per km (positive when
+/// temperature falls with height).
+pub fn lapse_rate(lower: &Level, upper: &Level) -> Option<f64> {
+    let depth = upper.height - lower.height;
+    if depth <= 0.0 {
+        return None;
+    }
+    Some((lower.temperature - upper.temperature) / depth * 1000.0)
+}
+
+/// George's K-index from the 850, 700 and 500 hPa levels.
+pub fn k_index(levels: &[Level]) -> Option<f64> {
+    let at = |pressure: u32| levels.iter().find(|level| level.pressure == pressure);
+    let (l850, l700, l500) = (at(850)?, at(700)?, at(500)?);
+    Some(
+        (l850.temperature - l500.temperature) + l850.dew_point
+            - (l700.temperature - l700.dew_point),
+    )
+}
+
+/// Pressure levels shown by `get_upper_air` when none are requested, in hPa.
+pub const DEFAULT_UPPER_AIR_LEVELS: [u32; 6] = [1000, 925, 850, 700, 500, 300];
+
+/// Levels always fetched for the derived indicators (lapse rate, K-index).
+pub const INDEX_LEVELS: [u32; 3] = [850, 700, 500];
+
+/// Variables fetched for every pressure level.
+const PRESSURE_LEVEL_VARIABLES: [&str; 6] = [
+    "temperature",
+    "dew_point",
+    "relative_humidity",
+    "wind_speed",
+    "wind_direction",
+    "geopotential_height",
+];
+
+impl OpenMeteoServer {
+    pub async fn fetch_upper_air(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        levels: &[u32],
+        hours: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let variables: Vec<String> = levels
+            .iter()
+            .flat_map(|level| {
+                PRESSURE_LEVEL_VARIABLES
+                    .iter()
+                    .map(move |variable| format!("{}_{}hPa", variable, level))
+            })
+            .collect();
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&hourly={}&forecast_hours={}{}",
+            self.base_url(Api::Forecast),
+            latitude,
+            longitude,
+            variables.join(","),
+            hours,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo API").await
+    }
+
+    pub fn format_upper_air(
+        &self,
+        data: &Value,
+        place: &Place,
+        levels: &[u32],
+        units: Units,
+    ) -> String {
+        let hourly = data.get("hourly").unwrap_or(&Value::Null);
+        let hourly_units = data.get("hourly_units").unwrap_or(&Value::Null);
+        let value_at = |variable: &str, level: u32, i: usize| {
+            hourly
+                .get(format!("{}_{}hPa", variable, level))
+                .and_then(|series| series.get(i))
+                .and_then(|v| v.as_f64())
+        };
+        let unit = |variable: &str, fallback: &'static str| {
+            hourly_units
+                .get(format!("{}_{}hPa", variable, levels[0]))
+                .and_then(|v| v.as_str())
+                .unwrap_or(fallback)
+                .to_string()
+        };
+        let temp_unit = unit("temperature", units.temperature.label());
+        let wind_unit = unit("wind_speed", units.wind_speed.label());
+
+        let empty_vec = vec![];
+        let times = hourly
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let mut report = format!(
+            "🎈 Upper-Air Forecast\nLocation: {}{}{}\nLevels: {} hPa\n",
+            place,
+            self.format_elevation(data),
+            self.format_timezone(data),
+            levels
+                .iter()
+                .map(|level| level.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        for (i, time) in times.iter().enumerate() {
+            let time = time.as_str().unwrap_or("Unknown");
+
+            // Sounding from the bottom up in °C, for the derived indicators.
+            let mut sounding: Vec<Level> = PRESSURE_LEVELS
+                .iter()
+                .filter_map(|&pressure| {
+                    Some(Level {
+                        pressure,
+                        temperature: units.temperature.to_celsius(value_at(
+                            "temperature",
+                            pressure,
+                            i,
+                        )?),
+                        dew_point: units.temperature.to_celsius(value_at(
+                            "dew_point",
+                            pressure,
+                            i,
+                        )?),
+                        height: value_at("geopotential_height", pressure, i)?,
+                    })
+                })
+                .collect();
+            sounding.sort_by(|a, b| a.height.total_cmp(&b.height));
+
+            let freezing_level = match freezing_level(&sounding) {
+                Some(height) => format!("{:.0}m", height),
+                None if sounding.first().is_some_and(|l| l.temperature < 0.0) => {
+                    "below the lowest level".to_string()
+                }
+                None if sounding.is_empty() => "n/a".to_string(),
+                None => "above the highest level".to_string(),
+            };
+            let at = |pressure: u32| sounding.iter().find(|l| l.pressure == pressure);
+            let lapse_rate = match at(850)
+                .zip(at(500))
+                .and_then(|(lower, upper)| lapse_rate(lower, upper))
+            {
+                Some(rate) => format!("{:.1}°C/km", rate),
+                None => "n/a".to_string(),
+            };
+            let k_index = match k_index(&sounding) {
+                Some(k) => format!("{:.0} ({})", k, self.get_k_index_category(k)),
+                None => "n/a".to_string(),
+            };
+
+            report.push_str(&format!(
+                "\n🕐 {}\n❄️ Freezing level: {}\n📉 Lapse rate 850-500 hPa: {}\n⚡ K-index: {}\n",
+                time, freezing_level, lapse_rate, k_index
+            ));
+
+            for &level in levels {
+                let fmt_value = |variable: &str, precision: usize, unit: &str| match value_at(
+                    variable, level, i,
+                ) {
+                    Some(v) => format!("{:.*}{}", precision, v, unit),
+                    None => "n/a".to_string(),
+                };
+                report.push_str(&format!(
+                    "  {} hPa ({}): {}, dew point {}, RH {}, wind {} from {}\n",
+                    level,
+                    fmt_value("geopotential_height", 0, "m"),
+                    fmt_value("temperature", 1, &temp_unit),
+                    fmt_value("dew_point", 1, &temp_unit),
+                    fmt_value("relative_humidity", 0, "%"),
+                    fmt_value("wind_speed", 0, &wind_unit),
+                    fmt_value("wind_direction", 0, "°"),
+                ));
+            }
+        }
+
+        report
+    }
+
+    fn get_k_index_category(&self, k: f64) -> &'static str {
+        match k {
+            k if k < 20.0 => "thunderstorms unlikely",
+            k if k < 26.0 => "isolated thunderstorms",
+            k if k < 31.0 => "widely scattered thunderstorms",
+            k if k <= 35.0 => "scattered thunderstorms",
+            _ => "numerous thunderstorms",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(pressure: u32, temperature: f64, dew_point: f64, height: f64) -> Level {
+        Level {
+            pressure,
+            temperature,
+            dew_point,
+            height,
+        }
+    }
+
+    #[test]
+    fn freezing_level_interpolates_between_the_levels_around_zero() {
+        let levels = [
+            level(1000, 10.0, 5.0, 100.0),
+            level(850, 4.0, 0.0, 1500.0),
+            level(700, -4.0, -10.0, 3000.0),
+            level(500, -20.0, -30.0, 5600.0),
+        ];
+        assert_eq!(freezing_level(&levels), Some(2250.0));
+
+        // Below a warm layer aloft, the crossing above that layer counts.
+        let levels = [
+            level(1000, -2.0, -4.0, 100.0),
+            level(850, 3.0, -1.0, 1500.0),
+            level(700, -5.0, -9.0, 3000.0),
+        ];
+        assert_eq!(freezing_level(&levels), Some(2062.5));
+    }
+
+    #[test]
+    fn freezing_level_is_none_without_a_crossing_in_the_column() {
+        let warm = [
+            level(1000, 30.0, 20.0, 100.0),
+            level(500, 2.0, -5.0, 5800.0),
+        ];
+        assert_eq!(freezing_level(&warm), None);
+        let cold = [
+            level(1000, -10.0, -12.0, 100.0),
+            level(500, -40.0, -45.0, 5300.0),
+        ];
+        assert_eq!(freezing_level(&cold), None);
+        assert_eq!(freezing_level(&warm[..1]), None);
+    }
+
+    #[test]
+    fn lapse_rate_is_per_km_and_needs_rising_height() {
+        let lower = level(850, 10.0, 5.0, 1500.0);
+        let upper = level(700, 0.25, -5.0, 3000.0);
+        assert_eq!(lapse_rate(&lower, &upper), Some(6.5));
+        assert_eq!(lapse_rate(&upper, &lower), None);
+    }
+
+    #[test]
+    fn k_index_matches_a_hand_computed_sounding() {
+        let levels = [
+            level(850, 20.0, 15.0, 1500.0),
+            level(700, 8.0, 2.0, 3100.0),
+            level(500, -10.0, -25.0, 5800.0),
+        ];
+        // (20 - -10) + 15 - (8 - 2)
+        assert_eq!(k_index(&levels), Some(39.0));
+    }
+
+    #[test]
+    fn k_index_is_none_when_a_level_is_missing() {
+        let levels = [level(850, 20.0, 15.0, 1500.0), level(700, 8.0, 2.0, 3100.0)];
+        assert_eq!(k_index(&levels), None);
+    }
+}
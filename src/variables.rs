@@ -0,0 +1,336 @@
+//! Catalog of Open-Meteo variables that can be requested explicitly.
+
+/// Hourly variables of both the forecast and archive APIs. Every hourly
+/// forecast variable is also available as a current condition.
+const HOURLY_VARIABLES: &[&str] = &[
+    "temperature_2m",
+    "relative_humidity_2m",
+    "dew_point_2m",
+    "apparent_temperature",
+    "pressure_msl",
+    "surface_pressure",
+    "cloud_cover",
+    "cloud_cover_low",
+    "cloud_cover_mid",
+    "cloud_cover_high",
+    "is_day",
+    "weather_code",
+    "precipitation",
+    "rain",
+    "snowfall",
+    "snow_depth",
+    "wind_speed_10m",
+    "wind_speed_100m",
+    "wind_direction_10m",
+    "wind_direction_100m",
+    "wind_gusts_10m",
+    "shortwave_radiation",
+    "direct_radiation",
+    "diffuse_radiation",
+    "direct_normal_irradiance",
+    "global_tilted_irradiance",
+    "terrestrial_radiation",
+    "sunshine_duration",
+    "boundary_layer_height",
+    "et0_fao_evapotranspiration",
+    "vapour_pressure_deficit",
+    "soil_temperature_0_to_7cm",
+    "soil_temperature_7_to_28cm",
+    "soil_temperature_28_to_100cm",
+    "soil_temperature_100_to_255cm",
+    "soil_moisture_0_to_7cm",
+    "soil_moisture_7_to_28cm",
+    "soil_moisture_28_to_100cm",
+    "soil_moisture_100_to_255cm",
+];
+
+/// Hourly variables only the forecast API serves.
+const FORECAST_HOURLY_VARIABLES: &[&str] = &[
+    "visibility",
+    "precipitation_probability",
+    "showers",
+    "freezing_level_height",
+    "wind_speed_80m",
+    "wind_speed_120m",
+    "wind_speed_180m",
+    "wind_direction_80m",
+    "wind_direction_120m",
+    "wind_direction_180m",
+    "temperature_80m",
+    "temperature_120m",
+    "temperature_180m",
+    "uv_index",
+    "uv_index_clear_sky",
+    "cape",
+    "lifted_index",
+    "convective_inhibition",
+    "evapotranspiration",
+    "soil_temperature_0cm",
+    "soil_temperature_6cm",
+    "soil_temperature_18cm",
+    "soil_temperature_54cm",
+    "soil_moisture_0_to_1cm",
+    "soil_moisture_1_to_3cm",
+    "soil_moisture_3_to_9cm",
+    "soil_moisture_9_to_27cm",
+    "soil_moisture_27_to_81cm",
+];
+
+/// Daily aggregates of both the forecast and archive APIs.
+const DAILY_VARIABLES: &[&str] = &[
+    "weather_code",
+    "temperature_2m_max",
+    "temperature_2m_min",
+    "temperature_2m_mean",
+    "apparent_temperature_max",
+    "apparent_temperature_min",
+    "apparent_temperature_mean",
+    "sunrise",
+    "sunset",
+    "daylight_duration",
+    "sunshine_duration",
+    "precipitation_sum",
+    "rain_sum",
+    "snowfall_sum",
+    "precipitation_hours",
+    "wind_speed_10m_max",
+    "wind_speed_10m_mean",
+    "wind_gusts_10m_max",
+    "wind_gusts_10m_mean",
+    "wind_direction_10m_dominant",
+    "shortwave_radiation_sum",
+    "et0_fao_evapotranspiration",
+    "relative_humidity_2m_max",
+    "relative_humidity_2m_min",
+    "relative_humidity_2m_mean",
+    "dew_point_2m_mean",
+    "cloud_cover_max",
+    "cloud_cover_min",
+    "cloud_cover_mean",
+    "pressure_msl_mean",
+    "vapour_pressure_deficit_max",
+];
+
+/// Daily aggregates only the forecast API serves.
+const FORECAST_DAILY_VARIABLES: &[&str] = &[
+    "uv_index_max",
+    "uv_index_clear_sky_max",
+    "showers_sum",
+    "precipitation_probability_max",
+    "precipitation_probability_mean",
+    "precipitation_probability_min",
+];
+
+/// API a variable is requested from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Forecast,
+    Archive,
+}
+
+impl Source {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Forecast => "forecast",
+            Self::Archive => "historical",
+        }
+    }
+}
+
+/// Response block a variable belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Block {
+    Current,
+    Hourly,
+    Daily,
+}
+
+impl Block {
+    /// Query parameter and response key of the block.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Current => "current",
+            Self::Hourly => "hourly",
+            Self::Daily => "daily",
+        }
+    }
+
+    /// Response key of the block's unit table.
+    pub fn units_key(self) -> &'static str {
+        match self {
+            Self::Current => "current_units",
+            Self::Hourly => "hourly_units",
+            Self::Daily => "daily_units",
+        }
+    }
+
+    /// Variables of the block that `source` serves.
+    fn catalog(self, source: Source) -> impl Iterator<Item = &'static &'static str> {
+        let (shared, forecast_only) = match self {
+            Self::Current | Self::Hourly => (HOURLY_VARIABLES, FORECAST_HOURLY_VARIABLES),
+            Self::Daily => (DAILY_VARIABLES, FORECAST_DAILY_VARIABLES),
+        };
+        let forecast_only = match source {
+            Source::Forecast => forecast_only,
+            Source::Archive => &[],
+        };
+        shared.iter().chain(forecast_only)
+    }
+}
+
+/// A validated variable and the block it is requested in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Variable {
+    pub block: Block,
+    pub name: &'static str,
+}
+
+/// Looks up each name in the catalogs of `blocks` that `source` serves, in
+/// order of preference.
+pub fn resolve(
+    names: &[String],
+    blocks: &[Block],
+    source: Source,
+) -> Result<Vec<Variable>, String> {
+    let lookup = |name: &str, source: Source| {
+        blocks.iter().find_map(|&block| {
+            block
+                .catalog(source)
+                .find(|known| **known == name)
+                .map(|known| Variable { block, name: known })
+        })
+    };
+    names
+        .iter()
+        .map(|name| {
+            lookup(name, source).ok_or_else(|| {
+                if source != Source::Forecast && lookup(name, Source::Forecast).is_some() {
+                    return format!(
+                        "Variable '{}' is only available from the forecast API, not the {} API.",
+                        name,
+                        source.name()
+                    );
+                }
+                let kinds: Vec<&str> = blocks.iter().map(|block| block.key()).collect();
+                format!(
+                    "Unknown variable: '{}'. Must be a known Open-Meteo {} {} variable (see the README for the list).",
+                    name,
+                    source.name(),
+                    kinds.join(" or ")
+                )
+            })
+        })
+        .collect()
+}
+
+/// `<block>=<defaults>,<extras>` query parameter, skipping extras that are
+/// already among the defaults. Empty when there is nothing to request.
+pub fn query(block: Block, defaults: &[&str], extras: &[Variable]) -> String {
+    let mut names: Vec<&str> = defaults.to_vec();
+    for variable in extras.iter().filter(|v| v.block == block) {
+        if !names.contains(&variable.name) {
+            names.push(variable.name);
+        }
+    }
+    if names.is_empty() {
+        String::new()
+    } else {
+        format!("&{}={}", block.key(), names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn resolve_prefers_the_first_block() {
+        let variables = resolve(
+            &names(&["sunshine_duration", "dew_point_2m"]),
+            &[Block::Daily, Block::Hourly],
+            Source::Forecast,
+        )
+        .unwrap();
+        assert_eq!(
+            variables,
+            [
+                Variable {
+                    block: Block::Daily,
+                    name: "sunshine_duration"
+                },
+                Variable {
+                    block: Block::Hourly,
+                    name: "dew_point_2m"
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_keeps_forecast_only_variables_out_of_the_archive() {
+        for name in [
+            "precipitation_probability",
+            "visibility",
+            "freezing_level_height",
+        ] {
+            assert!(resolve(&names(&[name]), &[Block::Hourly], Source::Forecast).is_ok());
+            let err = resolve(
+                &names(&[name]),
+                &[Block::Daily, Block::Hourly],
+                Source::Archive,
+            )
+            .unwrap_err();
+            assert!(
+                err.contains("only available from the forecast API"),
+                "{}",
+                err
+            );
+        }
+        assert!(resolve(
+            &names(&["soil_moisture_0_to_7cm"]),
+            &[Block::Hourly],
+            Source::Archive
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn resolve_rejects_unknown_names_and_wrong_blocks() {
+        let err = resolve(
+            &names(&["temperature_2m_max"]),
+            &[Block::Current],
+            Source::Forecast,
+        )
+        .unwrap_err();
+        assert!(
+            err.contains("Unknown variable: 'temperature_2m_max'"),
+            "{}",
+            err
+        );
+        assert!(resolve(&names(&["foo"]), &[Block::Daily], Source::Archive).is_err());
+    }
+
+    #[test]
+    fn query_merges_defaults_and_extras_of_the_block() {
+        let extras = resolve(
+            &names(&["temperature_2m_max", "rain_sum", "dew_point_2m"]),
+            &[Block::Daily, Block::Hourly],
+            Source::Forecast,
+        )
+        .unwrap();
+        assert_eq!(
+            query(
+                Block::Daily,
+                &["temperature_2m_max", "weather_code"],
+                &extras
+            ),
+            "&daily=temperature_2m_max,weather_code,rain_sum"
+        );
+        assert_eq!(query(Block::Hourly, &[], &extras), "&hourly=dew_point_2m");
+        assert_eq!(query(Block::Current, &[], &extras), "");
+    }
+}
@@ -0,0 +1,157 @@
+//! Forecast verification from the historical forecast and previous runs APIs.
+
+use crate::{
+    config::Api, stats::Summary, units::Units, DailySeries, OpenMeteoServer, Place, QueryOptions,
+};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Daily variables compared in verification reports as (archive key, label).
+pub const VERIFICATION_VARIABLES: [(&str, &str); 4] = [
+    ("temperature_2m_max", "Max temperature"),
+    ("temperature_2m_min", "Min temperature"),
+    ("precipitation_sum", "Precipitation"),
+    ("wind_speed_10m_max", "Max wind speed"),
+];
+
+impl OpenMeteoServer {
+    pub async fn fetch_historical_forecast(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start_date: &str,
+        end_date: &str,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&start_date={}&end_date={}&daily=temperature_2m_max,temperature_2m_min,precipitation_sum,wind_speed_10m_max{}",
+            self.base_url(Api::HistoricalForecast),
+            latitude,
+            longitude,
+            start_date,
+            end_date,
+            options.to_query()
+        );
+
+        self.get_json(&url, "OpenMeteo Historical Forecast API")
+            .await
+    }
+
+    pub async fn fetch_previous_model_runs(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        start_date: &str,
+        end_date: &str,
+        lead_days: u32,
+        options: &QueryOptions,
+    ) -> Result<Value, anyhow::Error> {
+        let url = format!(
+            "{}/v1/forecast?latitude={}&longitude={}&start_date={}&end_date={}&hourly=temperature_2m_previous_day{lead},precipitation_previous_day{lead},wind_speed_10m_previous_day{lead}{}",
+            self.base_url(Api::PreviousRuns),
+            latitude,
+            longitude,
+            start_date,
+            end_date,
+            options.to_query(),
+            lead = lead_days
+        );
+
+        self.get_json(&url, "OpenMeteo Previous Runs API").await
+    }
+
+    /// Reduces an hourly series to daily values, keeping only complete days.
+    pub fn daily_from_hourly(
+        &self,
+        data: &Value,
+        key: &str,
+        reduce: fn(&Summary) -> f64,
+    ) -> DailySeries {
+        let hourly = data.get("hourly").unwrap_or(&Value::Null);
+        let empty_vec = vec![];
+        let times = hourly
+            .get("time")
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+        let values = hourly
+            .get(key)
+            .and_then(|v| v.as_array())
+            .unwrap_or(&empty_vec);
+
+        let mut days: BTreeMap<String, Summary> = BTreeMap::new();
+        for (time, value) in times.iter().zip(values) {
+            let (Some(time), Some(value)) = (time.as_str(), value.as_f64()) else {
+                continue;
+            };
+            let day = time.split_once('T').map(|(day, _)| day).unwrap_or(time);
+            days.entry(day.to_string()).or_default().push(value);
+        }
+
+        days.into_iter()
+            .filter(|(_, summary)| summary.count == 24)
+            .map(|(day, summary)| (day, reduce(&summary)))
+            .collect()
+    }
+
+    pub fn format_verification(
+        &self,
+        title: &str,
+        place: &Place,
+        forecast: &[DailySeries],
+        archive: &Value,
+        units: Units,
+    ) -> String {
+        let daily_units = archive.get("daily_units").unwrap_or(&Value::Null);
+
+        let mut report = format!(
+            "🎯 {}\nLocation: {}{}\nObservations: historical archive (reanalysis)\n\n📊 Verification (forecast − observed):\n",
+            title,
+            place,
+            self.format_timezone(archive)
+        );
+
+        let mut rows: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+        let observed: Vec<DailySeries> = VERIFICATION_VARIABLES
+            .iter()
+            .map(|(key, _)| self.daily_series(archive, key))
+            .collect();
+
+        for (((key, label), predicted), actual) in
+            VERIFICATION_VARIABLES.iter().zip(forecast).zip(&observed)
+        {
+            let unit = daily_units
+                .get(*key)
+                .and_then(|v| v.as_str())
+                .unwrap_or(units.label_for(key));
+
+            let mut errors = Summary::default();
+            let mut abs_errors = Summary::default();
+            for (date, f) in predicted {
+                if let Some(o) = actual.get(date) {
+                    errors.push(f - o);
+                    abs_errors.push((f - o).abs());
+                    rows.entry(date.as_str())
+                        .or_default()
+                        .push(format!("{} {:.1}/{:.1}{}", label, f, o, unit));
+                }
+            }
+
+            match (errors.mean(), abs_errors.mean()) {
+                (Some(bias), Some(mae)) => report.push_str(&format!(
+                    "• {}: bias {:+.2}{}, MAE {:.2}{} ({} days)\n",
+                    label, bias, unit, mae, unit, errors.count
+                )),
+                _ => report.push_str(&format!("• {}: no overlapping data\n", label)),
+            }
+        }
+
+        if !rows.is_empty() {
+            report.push_str("\n📅 Daily forecast/observed:\n");
+            for (date, values) in rows {
+                report.push_str(&format!("{}: {}\n", date, values.join(", ")));
+            }
+        }
+
+        report
+    }
+}